@@ -0,0 +1,62 @@
+use salak::*;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Allocator counting allocations, used to report allocations per lookup.
+struct Counting;
+
+static ALLOCS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+fn allocs_per_lookup<F: FnMut()>(name: &str, mut f: F) {
+    const N: usize = 10000;
+    let start = ALLOCS.load(Ordering::Relaxed);
+    for _ in 0..N {
+        f();
+    }
+    let count = ALLOCS.load(Ordering::Relaxed) - start;
+    println!(
+        "{:<12} {:.2} allocations/lookup",
+        name,
+        count as f64 / N as f64
+    );
+}
+
+fn main() {
+    let env = Salak::builder()
+        .set("hello", "world")
+        .set("port", "8080")
+        .set_typed("typed_port", 8080u16)
+        .set("url", "http://${hello}")
+        .build()
+        .unwrap();
+
+    allocs_per_lookup("port", || {
+        let _ = env.require::<u16>(black_box("port"));
+    });
+    allocs_per_lookup("typed_port", || {
+        let _ = env.require::<u16>(black_box("typed_port"));
+    });
+    allocs_per_lookup("hello", || {
+        let _ = env.require::<Option<String>>(black_box("hello"));
+    });
+    allocs_per_lookup("placeholder", || {
+        let _ = env.require::<String>(black_box("url"));
+    });
+}
@@ -2,7 +2,13 @@ use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use salak::*;
 
 fn criterion_benchmark(c: &mut Criterion) {
-    let env = Salak::builder().set("hello", "world").build().unwrap();
+    let env = Salak::builder()
+        .set("hello", "world")
+        .set("port", "8080")
+        .set_typed("typed_port", 8080u16)
+        .set("url", "http://${hello}")
+        .build()
+        .unwrap();
 
     c.bench_function("hello1", |b| {
         b.iter(|| env.require::<String>(black_box("hello")))
@@ -16,6 +22,16 @@ fn criterion_benchmark(c: &mut Criterion) {
         b.iter(|| env.require::<Option<String>>(black_box("world")))
     });
 
+    c.bench_function("port", |b| b.iter(|| env.require::<u16>(black_box("port"))));
+
+    c.bench_function("typed_port", |b| {
+        b.iter(|| env.require::<u16>(black_box("typed_port")))
+    });
+
+    c.bench_function("placeholder", |b| {
+        b.iter(|| env.require::<String>(black_box("url")))
+    });
+
     c.bench_function("rand", |b| {
         b.iter(|| env.require::<String>(black_box("random.u8")))
     });
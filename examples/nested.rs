@@ -0,0 +1,54 @@
+use std::collections::BTreeMap;
+
+use salak::*;
+
+#[derive(Debug)]
+struct Member {
+    host: String,
+    port: u16,
+}
+
+/// Groups of members, such as `groups.<name>[<index>].host`.
+#[derive(Debug)]
+struct Groups(BTreeMap<String, Vec<Member>>);
+
+impl FromEnvironment for Groups {
+    fn from_env(
+        _: Option<Property<'_>>,
+        env: &mut SalakContext<'_>,
+    ) -> Result<Self, PropertyError> {
+        let mut groups = BTreeMap::new();
+        for name in env.get_sub_keys().str_keys() {
+            let mut group = env.push_key(name);
+            let mut members = vec![];
+            if let Some(max) = group.get_sub_keys().max() {
+                for i in 0..=max {
+                    let mut member = group.push_index(i);
+                    if let Some(host) = member.require_def::<Option<String>>("host", None)? {
+                        let port = member.require_def("port", Some(Property::I(80)))?;
+                        members.push(Member { host, port });
+                    }
+                }
+            }
+            groups.insert(name.to_string(), members);
+        }
+        Ok(Groups(groups))
+    }
+}
+
+fn main() -> Result<(), PropertyError> {
+    let env = Salak::builder()
+        .set("groups.web[0].host", "10.0.0.1")
+        .set("groups.web[1].host", "10.0.0.2")
+        .set("groups.web[1].port", "8080")
+        .set("groups.db[0].host", "10.0.1.1")
+        .set("groups.db[0].port", "5432")
+        .build()?;
+    let groups = env.require::<Groups>("groups")?;
+    for (name, members) in groups.0 {
+        for m in members {
+            println!("{}: {}:{}", name, m.host, m.port);
+        }
+    }
+    Ok(())
+}
@@ -39,40 +39,59 @@ fn parse_lit(lit: Lit) -> String {
     }
 }
 
-fn parse_attribute_prefix(attrs: &[Attribute]) -> Option<String> {
+fn parse_attribute_prefix(attrs: &[Attribute], is_enum: bool) -> (Option<String>, bool) {
+    let mut prefix = None;
+    let mut numeric_only = false;
     for attr in attrs {
         if let Ok(Meta::List(list)) = attr.parse_meta() {
             if !is_salak(&list) {
                 continue;
             }
             for m in list.nested {
-                if let NestedMeta::Meta(Meta::NameValue(nv)) = m {
-                    if parse_path(nv.path) == "prefix" {
-                        match nv.lit {
-                            Lit::Str(s) => return Some(s.value()),
-                            _ => panic!("Only support string"),
+                match m {
+                    NestedMeta::Meta(Meta::NameValue(nv)) => {
+                        if parse_path(nv.path) == "prefix" {
+                            match nv.lit {
+                                Lit::Str(s) => prefix = Some(s.value()),
+                                _ => panic!("Only support string"),
+                            }
+                        } else {
+                            panic!("Only support prefix");
                         }
-                    } else {
-                        panic!("Only support prefix");
                     }
-                } else {
-                    panic!("Only support prefix=\"xxx\"");
+                    NestedMeta::Meta(Meta::Path(p))
+                        if is_enum && parse_path(p.clone()) == "numeric_only" =>
+                    {
+                        numeric_only = true;
+                    }
+                    _ => panic!("Only support prefix=\"xxx\""),
                 }
             }
         }
     }
-    None
+    (prefix, numeric_only)
 }
 
-fn disable_attribute_prefix_enum(attrs: &[Attribute]) {
+fn parse_variant_name(attrs: &[Attribute]) -> Option<String> {
+    let mut name = None;
     for attr in attrs {
         if let Ok(Meta::List(list)) = attr.parse_meta() {
             if !is_salak(&list) {
                 continue;
             }
-            panic!("Salak attribute is not supporting enum");
+            for m in list.nested {
+                match m {
+                    NestedMeta::Meta(Meta::NameValue(nv))
+                        if parse_path(nv.path.clone()) == "name" =>
+                    {
+                        name = Some(parse_lit(nv.lit));
+                    }
+                    _ => panic!("Only support name on enum variant"),
+                }
+            }
         }
     }
+    name
 }
 
 fn is_salak(list: &MetaList) -> bool {
@@ -82,13 +101,77 @@ fn is_salak(list: &MetaList) -> bool {
     false
 }
 
-fn parse_field_attribute(
-    attrs: Vec<Attribute>,
-    name: &mut Ident,
-) -> (quote::__private::TokenStream, quote::__private::TokenStream) {
+struct FieldAttr {
+    def: quote::__private::TokenStream,
+    def_desc: quote::__private::TokenStream,
+    deprecated: Option<String>,
+    aliases: Vec<String>,
+    min_len: Option<usize>,
+    max_len: Option<usize>,
+    trim: bool,
+    required: bool,
+}
+
+/// Inner type of `Option<T>`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    if let Type::Path(p) = ty {
+        if let Some(seg) = p.path.segments.last() {
+            if seg.ident == "Option" {
+                if let PathArguments::AngleBracketed(args) = &seg.arguments {
+                    if let Some(GenericArgument::Type(ty)) = args.args.first() {
+                        return Some(ty);
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Best-effort compile time check of default value for primitive types,
+/// other types and placeholders are checked when parsing.
+fn check_default(ty: &Type, def: &str) {
+    if let Some(ty) = option_inner(ty) {
+        return check_default(ty, def);
+    }
+    let seg = match ty {
+        Type::Path(p) => match p.path.segments.last() {
+            Some(seg) => seg,
+            _ => return,
+        },
+        _ => return,
+    };
+    if def.is_empty() || def.contains('$') {
+        return;
+    }
+    macro_rules! check {
+        ($($x:ident)+) => {
+            match &seg.ident.to_string()[..] {
+                $(stringify!($x) => def.parse::<$x>().is_ok(),)+
+                "bool" => matches!(def, "yes" | "true" | "no" | "false"),
+                _ => true,
+            }
+        };
+    }
+    if !check!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize f32 f64) {
+        panic!("Invalid default value {:?} for type {}", def, seg.ident);
+    }
+}
+
+fn parse_field_attribute(attrs: Vec<Attribute>, ty: &Type, name: &mut Ident) -> FieldAttr {
     let mut def = None;
     let mut rename = None;
     let mut desc = None;
+    let mut deprecated = None;
+    let mut aliases = vec![];
+    let mut min_len = None;
+    let mut max_len = None;
+    let mut trim = false;
+    let mut required = false;
+    let parse_len = |lit: Lit| match parse_lit(lit).parse::<usize>() {
+        Ok(v) => Some(v),
+        Err(_) => panic!("Only support usize for min_len/max_len"),
+    };
     for attr in attrs {
         if let Ok(Meta::List(list)) = attr.parse_meta() {
             if !is_salak(&list) {
@@ -100,10 +183,24 @@ fn parse_field_attribute(
                         "default" => def = Some(parse_lit(nv.lit)),
                         "name" => rename = Some(parse_lit(nv.lit)),
                         "desc" => desc = Some(parse_lit(nv.lit)),
-                        _ => panic!("Only support default/name/desc"),
+                        "deprecated" => deprecated = Some(parse_lit(nv.lit)),
+                        "alias" => aliases.push(parse_lit(nv.lit)),
+                        "min_len" => min_len = parse_len(nv.lit),
+                        "max_len" => max_len = parse_len(nv.lit),
+                        _ => panic!(
+                            "Only support default/name/desc/deprecated/alias/min_len/max_len/trim/required"
+                        ),
+                    }
+                } else if let NestedMeta::Meta(Meta::Path(p)) = m {
+                    match &parse_path(p)[..] {
+                        "trim" => trim = true,
+                        "required" => required = true,
+                        _ => panic!(
+                            "Only support default/name/desc/deprecated/alias/min_len/max_len/trim/required"
+                        ),
                     }
                 } else {
-                    panic!("Only support NestedMeta::Meta(Meta::NameValue)");
+                    panic!("Only support NestedMeta::Meta(Meta::NameValue) or NestedMeta::Meta(Meta::Path)");
                 }
             }
         }
@@ -111,6 +208,12 @@ fn parse_field_attribute(
     if let Some(rename) = rename {
         *name = quote::format_ident!("{}", rename);
     }
+    if let Some(def) = &def {
+        if required {
+            panic!("required conflicts with default");
+        }
+        check_default(ty, def);
+    }
 
     let (a, b) = match def {
         Some(def) => (
@@ -121,6 +224,14 @@ fn parse_field_attribute(
                 Some(false), Some(#def)
             },
         ),
+        _ if required => (
+            quote! {
+                None
+            },
+            quote! {
+                Some(true), None
+            },
+        ),
         _ => (
             quote! {
                 None
@@ -131,9 +242,15 @@ fn parse_field_attribute(
         ),
     };
 
-    (
-        a,
-        if let Some(desc) = desc {
+    let desc = match (desc, &deprecated) {
+        (Some(desc), Some(dep)) => Some(format!("[Deprecated: {}] {}", dep, desc)),
+        (None, Some(dep)) => Some(format!("[Deprecated: {}]", dep)),
+        (desc, None) => desc,
+    };
+
+    FieldAttr {
+        def: a,
+        def_desc: if let Some(desc) = desc {
             quote! {
                 #b, Some(#desc.to_string())
             }
@@ -142,17 +259,93 @@ fn parse_field_attribute(
                 #b, None
             }
         },
-    )
+        deprecated,
+        aliases,
+        min_len,
+        max_len,
+        trim,
+        required,
+    }
 }
 
 fn derive_field(field: Field) -> (quote::__private::TokenStream, quote::__private::TokenStream) {
     let name = field.ident.expect("Not possible");
     let ty = field.ty;
     let mut rename = name.clone();
-    let (def, def_desc) = parse_field_attribute(field.attrs, &mut rename);
+    let FieldAttr {
+        def,
+        def_desc,
+        deprecated,
+        aliases,
+        min_len,
+        max_len,
+        trim,
+        required,
+    } = parse_field_attribute(field.attrs, &ty, &mut rename);
+    // Required option field parses inner type, so `NotFound` is not swallowed.
+    let inner = match option_inner(&ty) {
+        Some(inner) if required => Some(inner),
+        _ => None,
+    };
+    let req_ty = inner.unwrap_or(&ty);
+    let warn = match deprecated {
+        Some(dep) => quote! {
+            env.warn_deprecated(stringify!(#rename), #dep);
+        },
+        _ => quote! {},
+    };
+    let require = if trim {
+        quote! {
+            env.require_trim::<#req_ty>(&[#(#aliases),*], stringify!(#rename), #def)?
+        }
+    } else if aliases.is_empty() {
+        quote! {
+            env.require_def::<#req_ty>(stringify!(#rename), #def)?
+        }
+    } else {
+        quote! {
+            env.require_alias::<#req_ty>(&[#(#aliases),*], stringify!(#rename), #def)?
+        }
+    };
+    let require = if inner.is_some() {
+        quote! { Some(#require) }
+    } else {
+        require
+    };
+    let require = if min_len.is_none() && max_len.is_none() {
+        require
+    } else {
+        let min = match min_len {
+            Some(v) => quote! { Some(#v) },
+            _ => quote! { None },
+        };
+        let max = match max_len {
+            Some(v) => quote! { Some(#v) },
+            _ => quote! { None },
+        };
+        let check = quote! { env.check_len(stringify!(#rename), v.len(), #min, #max)?; };
+        if option_inner(&ty).is_some() {
+            quote! {{
+                let val = #require;
+                if let Some(v) = &val {
+                    #check
+                }
+                val
+            }}
+        } else {
+            quote! {{
+                let v = #require;
+                #check
+                v
+            }}
+        }
+    };
     (
         quote! {
-            #name: env.require_def::<#ty>(stringify!(#rename), #def)?
+            #name: {
+                #warn
+                #require
+            }
         },
         quote! {
             env.add_key_desc::<#ty>(stringify!(#rename), #def_desc);
@@ -179,10 +372,28 @@ fn derive_fields(
     panic!("Only support named body");
 }
 
-fn derive_struct(name: &Ident, data: DataStruct) -> quote::__private::TokenStream {
+fn with_bound(generics: &Generics, bound: quote::__private::TokenStream) -> Generics {
+    let mut generics = generics.clone();
+    let params: Vec<Ident> = generics.type_params().map(|t| t.ident.clone()).collect();
+    let where_clause = generics.make_where_clause();
+    for param in params {
+        where_clause.predicates.push(parse_quote!(#param: #bound));
+    }
+    generics
+}
+
+fn derive_struct(
+    name: &Ident,
+    generics: &Generics,
+    data: DataStruct,
+) -> quote::__private::TokenStream {
     let (field, field_desc) = derive_fields(data.fields);
+    let gen = with_bound(generics, quote! { FromEnvironment });
+    let (impl_generics, ty_generics, where_clause) = gen.split_for_impl();
+    let desc_gen = with_bound(generics, quote! { DescFromEnvironment });
+    let (desc_impl_generics, _, desc_where_clause) = desc_gen.split_for_impl();
     quote! {
-        impl FromEnvironment for #name {
+        impl #impl_generics FromEnvironment for #name #ty_generics #where_clause {
             fn from_env(
                 val: Option<Property<'_>>,
                 env: &mut SalakContext<'_>,
@@ -193,7 +404,7 @@ fn derive_struct(name: &Ident, data: DataStruct) -> quote::__private::TokenStrea
             }
         }
 
-        impl DescFromEnvironment for #name {
+        impl #desc_impl_generics DescFromEnvironment for #name #ty_generics #desc_where_clause {
             fn key_desc(env: &mut SalakDescContext<'_>) {
                 #(#field_desc)*
             }
@@ -201,29 +412,59 @@ fn derive_struct(name: &Ident, data: DataStruct) -> quote::__private::TokenStrea
     }
 }
 
-fn derive_enum(type_name: &Ident, data: &DataEnum) -> quote::__private::TokenStream {
+fn derive_enum(
+    type_name: &Ident,
+    data: &DataEnum,
+    numeric_only: bool,
+) -> quote::__private::TokenStream {
     let mut vs = vec![];
+    let mut ns = vec![];
+    let mut names = vec![];
+    let mut idents = vec![];
     for variant in &data.variants {
-        disable_attribute_prefix_enum(&variant.attrs);
-        let lname = quote::format_ident!("{}", format!("{}", variant.ident).to_lowercase());
+        let lname = parse_variant_name(&variant.attrs)
+            .unwrap_or_else(|| variant.ident.to_string())
+            .to_lowercase();
         let name = &variant.ident;
-        let body = match variant.fields {
+        match variant.fields {
             Fields::Unit => {
-                quote! {
-                    stringify!(#lname) => Ok(#type_name::#name),
+                if !numeric_only {
+                    vs.push(quote! {
+                        #lname => Ok(#type_name::#name),
+                    });
                 }
+                names.push(lname);
+                idents.push(name);
+                ns.push(quote! {
+                    if n == #type_name::#name as i128 {
+                        return Ok(#type_name::#name);
+                    }
+                });
             }
             _ => panic!("Enum only support no field pattern."),
         };
-        vs.push(body);
     }
+    let expected = if numeric_only {
+        quote! { [#((#type_name::#idents as i128).to_string()),*].join(", ") }
+    } else {
+        let names = names.join(", ");
+        quote! { #names }
+    };
     quote! {
         impl EnumProperty for #type_name {
             #[inline]
             fn str_to_enum(val: &str) -> Result<#type_name, PropertyError>{
+            let fail = || PropertyError::parse_fail(&format!(
+                "invalid enum value '{}', expected one of: {}",
+                val, #expected
+            ));
+            if let Ok(n) = val.trim().parse::<i128>() {
+                #(#ns)*
+                return Err(fail());
+            }
             match &val.to_lowercase()[..] {
                 #(#vs)*
-                _ => Err(PropertyError::parse_fail("invalid enum value")),
+                _ => Err(fail()),
             }
             }
         }
@@ -235,35 +476,82 @@ fn derive_enum(type_name: &Ident, data: &DataEnum) -> quote::__private::TokenStr
 pub fn from_env_derive(input: TokenStream) -> TokenStream {
     let input: DeriveInput = parse_macro_input!(input as DeriveInput);
     let name = input.ident;
-    let (head, body) = match input.data {
-        Data::Struct(d) => (
-            if let Some(prefix) = parse_attribute_prefix(&input.attrs) {
-                quote! {
-                        impl PrefixedFromEnvironment for #name {
-                        fn prefix() -> &'static str {
-                            #prefix
-                        }
-                    }
+    let generics = input.generics;
+    let (prefix, numeric_only) =
+        parse_attribute_prefix(&input.attrs, matches!(input.data, Data::Enum(_)));
+    let head = if let Some(prefix) = prefix {
+        let gen = with_bound(&generics, quote! { DescFromEnvironment });
+        let (impl_generics, ty_generics, where_clause) = gen.split_for_impl();
+        quote! {
+                impl #impl_generics PrefixedFromEnvironment for #name #ty_generics #where_clause {
+                fn prefix() -> &'static str {
+                    #prefix
                 }
-            } else {
-                quote! {}
-            },
-            derive_struct(&name, d),
-        ),
-        Data::Enum(d) => {
-            disable_attribute_prefix_enum(&input.attrs);
-            (quote! {}, derive_enum(&name, &d))
+            }
         }
+    } else {
+        quote! {}
+    };
+    let body = match input.data {
+        Data::Struct(d) => derive_struct(&name, &generics, d),
+        Data::Enum(d) => derive_enum(&name, &d, numeric_only),
         _ => panic!("union is not supported"),
     };
 
+    let gen = with_bound(&generics, quote! { FromEnvironment });
+    let (impl_generics, ty_generics, where_clause) = gen.split_for_impl();
     TokenStream::from(quote! {
-        impl AutoDeriveFromEnvironment for #name {}
+        impl #impl_generics AutoDeriveFromEnvironment for #name #ty_generics #where_clause {}
         #head
         #body
     })
 }
 
+/// Derive [PrefixedFromEnvironment](https://docs.rs/salak/latest/salak/trait.PrefixedFromEnvironment.html)
+/// for type with hand-written `FromEnvironment`, `DescFromEnvironment` is also generated with
+/// no description.
+#[proc_macro_derive(Prefixed, attributes(salak))]
+pub fn prefixed_derive(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+    let prefix = match parse_attribute_prefix(&input.attrs, false).0 {
+        Some(prefix) => prefix,
+        _ => panic!("Prefixed requires prefix=\"xxx\""),
+    };
+    let gen = with_bound(&input.generics, quote! { DescFromEnvironment });
+    let (impl_generics, ty_generics, where_clause) = gen.split_for_impl();
+    let desc_gen = with_bound(&input.generics, quote! { FromEnvironment });
+    let (desc_impl_generics, _, desc_where_clause) = desc_gen.split_for_impl();
+    TokenStream::from(quote! {
+        impl #impl_generics PrefixedFromEnvironment for #name #ty_generics #where_clause {
+            fn prefix() -> &'static str {
+                #prefix
+            }
+        }
+
+        impl #desc_impl_generics DescFromEnvironment for #name #ty_generics #desc_where_clause {
+            fn key_desc(_: &mut SalakDescContext<'_>) {}
+        }
+    })
+}
+
+/// Derive [DescFromEnvironment](https://docs.rs/salak/latest/salak/trait.DescFromEnvironment.html)
+/// for type with hand-written `FromEnvironment`, describing it as a single property.
+#[proc_macro_derive(DescFromEnvironment)]
+pub fn desc_derive(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    let name = input.ident;
+    let gen = with_bound(&input.generics, quote! { FromEnvironment });
+    let (impl_generics, ty_generics, where_clause) = gen.split_for_impl();
+    TokenStream::from(quote! {
+        impl #impl_generics DescFromEnvironment for #name #ty_generics #where_clause {
+            fn key_desc(env: &mut SalakDescContext<'_>) {
+                env.leaf_desc();
+            }
+        }
+    })
+}
+
 struct ServiceAttr {
     namespace: Option<String>,
     access: Option<u8>,
@@ -301,6 +589,28 @@ fn service_parse_field_attribute(attrs: Vec<Attribute>) -> ServiceAttr {
     sa
 }
 
+fn service_parse_default_namespace(attrs: &[Attribute]) -> Option<String> {
+    let mut namespace = None;
+    for attr in attrs {
+        if let Ok(Meta::List(list)) = attr.parse_meta() {
+            if !is_salak(&list) {
+                continue;
+            }
+            for m in list.nested {
+                match m {
+                    NestedMeta::Meta(Meta::NameValue(nv))
+                        if parse_path(nv.path.clone()) == "default_namespace" =>
+                    {
+                        namespace = Some(parse_lit(nv.lit));
+                    }
+                    _ => panic!("Only support default_namespace on service"),
+                }
+            }
+        }
+    }
+    namespace
+}
+
 fn get_generic_type<'a>(ty: &'a Type, name: &str) -> (bool, &'a Type) {
     match ty {
         Type::Path(v) => {
@@ -322,10 +632,11 @@ fn get_generic_type<'a>(ty: &'a Type, name: &str) -> (bool, &'a Type) {
 
 fn service_derive_field(
     field: Field,
+    default_namespace: &str,
 ) -> (quote::__private::TokenStream, quote::__private::TokenStream) {
     let name = field.ident.expect("Not possible");
     let ServiceAttr { namespace, access } = service_parse_field_attribute(field.attrs);
-    let namespace = namespace.unwrap_or("".to_owned());
+    let namespace = namespace.unwrap_or_else(|| default_namespace.to_owned());
     let (is_option, ty) = get_generic_type(&field.ty, "Option");
     let (is_arc, ty) = get_generic_type(ty, "Arc");
     if !is_arc {
@@ -367,6 +678,7 @@ fn service_derive_field(
 
 fn service_derive_fields(
     fields: Fields,
+    default_namespace: &str,
 ) -> (
     Vec<quote::__private::TokenStream>,
     Vec<quote::__private::TokenStream>,
@@ -375,7 +687,7 @@ fn service_derive_fields(
         let mut v = vec![];
         let mut f = vec![];
         for field in fields.named {
-            let (x, y) = service_derive_field(field);
+            let (x, y) = service_derive_field(field, default_namespace);
             v.push(x);
             f.push(y);
         }
@@ -384,8 +696,12 @@ fn service_derive_fields(
     panic!("Only support named body");
 }
 
-fn service_derive_struct(name: &Ident, data: DataStruct) -> quote::__private::TokenStream {
-    let (field, fun) = service_derive_fields(data.fields);
+fn service_derive_struct(
+    name: &Ident,
+    data: DataStruct,
+    default_namespace: &str,
+) -> quote::__private::TokenStream {
+    let (field, fun) = service_derive_fields(data.fields, default_namespace);
     quote! {
         impl Service for #name {
             fn create(factory: &FactoryContext<'_>) -> Result<Self, PropertyError> {
@@ -403,12 +719,16 @@ fn service_derive_struct(name: &Ident, data: DataStruct) -> quote::__private::To
 }
 
 /// Derive [Service](https://docs.rs/salak/latest/salak/trait.Service.html).
+///
+/// Fields get resources by `#[salak(namespace = "...")]`, struct level
+/// `#[salak(default_namespace = "...")]` applies to fields without namespace.
 #[proc_macro_derive(Service, attributes(salak))]
 pub fn service_derive(input: TokenStream) -> TokenStream {
     let input: DeriveInput = parse_macro_input!(input as DeriveInput);
     let name = input.ident;
+    let default_namespace = service_parse_default_namespace(&input.attrs).unwrap_or_default();
     let body = match input.data {
-        Data::Struct(data) => service_derive_struct(&name, data),
+        Data::Struct(data) => service_derive_struct(&name, data, &default_namespace),
         _ => panic!("Only struct is supported"),
     };
     TokenStream::from(quote! {#body})
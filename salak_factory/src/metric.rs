@@ -34,6 +34,83 @@ impl Deref for Metric {
     }
 }
 
+/// Label of resource namespace.
+pub const LABEL_NAMESPACE: &str = "namespace";
+/// Label of resource name.
+pub const LABEL_NAME: &str = "name";
+/// Label of network.
+pub const LABEL_NETWORK: &str = "network";
+
+/// Count of connection tries of pool.
+pub const POOL_TRY_COUNT: &str = "thread_pool.connection.try_count";
+/// Count of connection failures of pool.
+pub const POOL_FAIL_COUNT: &str = "thread_pool.connection.fail_count";
+/// Connection latency of pool.
+pub const POOL_LATENCY: &str = "thread_pool.connection.latency";
+/// Max connections of pool.
+pub const POOL_MAX_COUNT: &str = "thread_pool.max_count";
+/// Min idle connections of pool.
+pub const POOL_MIN_IDLE_COUNT: &str = "thread_pool.min_idle_count";
+/// Idle connections of pool.
+pub const POOL_IDLE_COUNT: &str = "thread_pool.idle_count";
+/// Active connections of pool.
+pub const POOL_ACTIVE_COUNT: &str = "thread_pool.active_count";
+
+/// Namespace label value, empty namespace is `default`.
+pub fn namespace_label(namespace: &'static str) -> Label {
+    Label::new(
+        LABEL_NAMESPACE,
+        if namespace.is_empty() {
+            "default"
+        } else {
+            namespace
+        },
+    )
+}
+
+/// Metric handle with cached [`Key`], which avoids building key for every record.
+#[allow(missing_debug_implementations)]
+#[derive(Clone)]
+pub struct MetricHandle {
+    metric: Arc<Metric>,
+    key: Key,
+}
+
+impl MetricHandle {
+    /// Get key.
+    pub fn key(&self) -> &Key {
+        &self.key
+    }
+
+    /// Increment counter by 1.
+    pub fn inc(&self) {
+        self.add(1);
+    }
+
+    /// Increment counter by `val`.
+    pub fn add(&self, val: u64) {
+        if self.metric.enabled {
+            self.metric.recorder.increment_counter(&self.key, val);
+        }
+    }
+
+    /// Set gauge to `val`.
+    pub fn set(&self, val: f64) {
+        self.update(GaugeValue::Absolute(val));
+    }
+
+    /// Increment gauge by `val`.
+    pub fn increment(&self, val: f64) {
+        self.update(GaugeValue::Increment(val));
+    }
+
+    fn update(&self, val: GaugeValue) {
+        if self.metric.enabled {
+            self.metric.recorder.update_gauge(&self.key, val);
+        }
+    }
+}
+
 /// Turn any to key.
 pub trait AnyKey: Any + Resource {
     /// Create key from name and namespace.
@@ -41,15 +118,8 @@ pub trait AnyKey: Any + Resource {
         Key::from_parts(
             name,
             vec![
-                Label::new(
-                    "namespace",
-                    if namespace.is_empty() {
-                        "default"
-                    } else {
-                        namespace
-                    },
-                ),
-                Label::new("name", Self::Config::prefix()),
+                namespace_label(namespace),
+                Label::new(LABEL_NAME, Self::Config::prefix()),
             ],
         )
     }
@@ -80,7 +150,7 @@ macro_rules! gauge {
 macro_rules! gauge_network {
     ($metric:ident.$sys:ident.$fn:ident = $name:expr, $x:expr) => {
         $metric.gauge(
-            Key::from_parts($name, vec![Label::new("network", $x.to_owned())]),
+            Key::from_parts($name, vec![Label::new(LABEL_NETWORK, $x.to_owned())]),
             $sys.$fn() as f64,
         );
     };
@@ -104,6 +174,26 @@ impl Metric {
         self.recorder.increment_counter(&k.into(), val);
     }
 
+    /// Register counter and create its handle.
+    pub fn counter<K: Into<Key>>(self: &Arc<Self>, k: K) -> MetricHandle {
+        let key = k.into();
+        self.recorder.register_counter(&key, None, None);
+        MetricHandle {
+            metric: self.clone(),
+            key,
+        }
+    }
+
+    /// Register gauge with `unit` and create its handle.
+    pub fn gauge_handle<K: Into<Key>>(self: &Arc<Self>, k: K, unit: Option<Unit>) -> MetricHandle {
+        let key = k.into();
+        self.recorder.register_gauge(&key, unit, None);
+        MetricHandle {
+            metric: self.clone(),
+            key,
+        }
+    }
+
     /// Add listen state.
     pub fn add_listen_state(
         &self,
@@ -280,3 +370,47 @@ impl Resource for Metric {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metric_handle_test() {
+        let recorder = PrometheusBuilder::new().build();
+        let handle = recorder.handle();
+        let metric = Arc::new(Metric {
+            recorder,
+            handle,
+            code: Mutex::new(Vec::new()),
+            sys: Mutex::new(System::new()),
+            enabled: true,
+            networks: HashSet::new(),
+        });
+        let counter = metric.counter(Key::from_parts(POOL_TRY_COUNT, vec![namespace_label("")]));
+        counter.inc();
+        counter.add(2);
+        let gauge = metric.gauge_handle(
+            Key::from_parts(POOL_LATENCY, vec![namespace_label("")]),
+            Some(Unit::Microseconds),
+        );
+        gauge.set(1.0);
+        gauge.increment(2.0);
+        let render = metric.render().unwrap();
+        let value = |name: &str| {
+            render
+                .lines()
+                .find(|l| l.starts_with(name))
+                .and_then(|l| l.rsplit(' ').next())
+                .map(|v| v.to_string())
+        };
+        assert_eq!(
+            Some("3".to_string()),
+            value("thread_pool_connection_try_count")
+        );
+        assert_eq!(
+            Some("3".to_string()),
+            value("thread_pool_connection_latency")
+        );
+    }
+}
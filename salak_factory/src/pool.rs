@@ -5,7 +5,10 @@ pub(crate) use r2d2::{ManageConnection, Pool};
 use scheduled_thread_pool::ScheduledThreadPool;
 
 #[cfg(feature = "metric")]
-use crate::metric::{AnyKey, GaugeValue, Key, Label, Metric, Unit};
+use crate::metric::{
+    namespace_label, AnyKey, Key, Metric, MetricHandle, Unit, POOL_ACTIVE_COUNT, POOL_FAIL_COUNT,
+    POOL_IDLE_COUNT, POOL_LATENCY, POOL_MAX_COUNT, POOL_MIN_IDLE_COUNT, POOL_TRY_COUNT,
+};
 
 use super::*;
 pub(crate) use std::time::Duration;
@@ -55,6 +58,37 @@ pub struct PoolConfig {
         desc = "Wait for init when start pool."
     )]
     wait_for_init: bool,
+    #[salak(
+        default = "${pool.warmup_connections:}",
+        desc = "Connections established before pool is created."
+    )]
+    warmup_connections: Option<u32>,
+}
+
+/// Snapshot of pool statistics.
+#[cfg_attr(docsrs, doc(cfg(feature = "pool")))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStats {
+    /// The maximum number of connections.
+    pub max_size: u32,
+    /// The minimum idle connection count.
+    pub min_idle: Option<u32>,
+    /// Number of idle connections.
+    pub idle: u32,
+    /// Number of connections currently managed by the pool.
+    pub active: u32,
+}
+
+impl PoolStats {
+    pub(crate) fn new<M: ManageConnection>(pool: &Pool<M>) -> Self {
+        let state = pool.state();
+        Self {
+            max_size: pool.max_size(),
+            min_idle: pool.min_idle(),
+            idle: state.idle_connections,
+            active: state.connections,
+        }
+    }
 }
 
 macro_rules! set_option_field_return {
@@ -110,13 +144,14 @@ impl<M: ManageConnection> PoolCustomizer<M> {
 pub struct ManagedConnection<M> {
     inner: M,
     #[cfg(feature = "metric")]
-    try_count: Key,
-    #[cfg(feature = "metric")]
-    fail_count: Key,
-    #[cfg(feature = "metric")]
-    latency: Key,
-    #[cfg(feature = "metric")]
-    metric: Option<Arc<Metric>>,
+    metric: Option<PoolMetric>,
+}
+
+#[cfg(feature = "metric")]
+struct PoolMetric {
+    try_count: MetricHandle,
+    fail_count: MetricHandle,
+    latency: MetricHandle,
 }
 
 impl<M: ManageConnection> ManageConnection for ManagedConnection<M> {
@@ -128,17 +163,16 @@ impl<M: ManageConnection> ManageConnection for ManagedConnection<M> {
         #[cfg(feature = "metric")]
         if let Some(metric) = &self.metric {
             let now = std::time::SystemTime::now();
-            metric.increment_counter(&self.try_count, 1);
-            // metric.increment_counter(key, value);
+            metric.try_count.inc();
             let v = match self.inner.connect() {
                 Ok(v) => Ok(v),
                 Err(err) => {
-                    metric.increment_counter(&self.fail_count, 1);
+                    metric.fail_count.inc();
                     Err(err)
                 }
             };
             if let Ok(d) = std::time::SystemTime::now().duration_since(now) {
-                metric.update_gauge(&self.latency, GaugeValue::Increment(d.as_micros() as f64));
+                metric.latency.increment(d.as_micros() as f64);
             }
             v
         } else {
@@ -188,44 +222,50 @@ impl PoolConfig {
         set_option_field_return!(customize, build, event_handler);
         set_option_field_return!(customize, build, connection_customizer);
 
-        #[cfg(feature = "metric")]
-        let namespace = if _context.current_namespace().is_empty() {
-            "default"
-        } else {
-            _context.current_namespace()
-        };
-
         let m = ManagedConnection {
             inner: m,
             #[cfg(feature = "metric")]
-            try_count: Key::from_parts(
-                "thread_pool.connection.try_count",
-                vec![Label::new("namespace", namespace)],
-            ),
-            #[cfg(feature = "metric")]
-            fail_count: Key::from_parts(
-                "thread_pool.connection.fail_count",
-                vec![Label::new("namespace", namespace)],
-            ),
-            #[cfg(feature = "metric")]
-            latency: Key::from_parts(
-                "thread_pool.connection.latency",
-                vec![Label::new("namespace", namespace)],
-            ),
-            #[cfg(feature = "metric")]
-            metric: _context.get_optional_resource()?,
+            metric: _context.get_optional_resource::<Metric>()?.map(|metric| {
+                let key = |name: &'static str| {
+                    Key::from_parts(name, vec![namespace_label(_context.current_namespace())])
+                };
+                PoolMetric {
+                    try_count: metric.counter(key(POOL_TRY_COUNT)),
+                    fail_count: metric.counter(key(POOL_FAIL_COUNT)),
+                    latency: metric.gauge_handle(key(POOL_LATENCY), Some(Unit::Microseconds)),
+                }
+            }),
         };
 
-        #[cfg(feature = "metric")]
-        if let Some(metric) = &m.metric {
-            metric.register_gauge(&m.latency, Some(Unit::Microseconds), None);
+        let pool = if self.wait_for_init {
+            build.build(m)?
+        } else {
+            build.build_unchecked(m)
+        };
+        if let Some(warmup) = self.warmup_connections {
+            Self::warmup(&pool, warmup)?;
         }
+        Ok(pool)
+    }
 
-        if self.wait_for_init {
-            Ok(build.build(m)?)
-        } else {
-            Ok(build.build_unchecked(m))
+    /// Eagerly open `warmup` connections, all of them should be established
+    /// within connection timeout.
+    fn warmup<M: ManageConnection>(pool: &Pool<M>, warmup: u32) -> Result<(), PropertyError> {
+        if warmup > pool.max_size() {
+            return Err(PropertyError::parse_fail(
+                "warmup connections exceed pool max size",
+            ));
+        }
+        let timeout = pool.connection_timeout();
+        let now = std::time::Instant::now();
+        let mut conns = Vec::with_capacity(warmup as usize);
+        for _ in 0..warmup {
+            let remain = timeout
+                .checked_sub(now.elapsed())
+                .ok_or_else(|| PropertyError::parse_fail("warmup connections timeout"))?;
+            conns.push(pool.get_timeout(remain)?);
         }
+        Ok(())
     }
 
     #[cfg(feature = "metric")]
@@ -236,25 +276,17 @@ impl PoolConfig {
         if let Some(metric) = factory.get_optional_resource::<Metric>()? {
             let pool = pool.clone();
             let namespace = factory.current_namespace();
-            metric.gauge(
-                K::new_key("thread_pool.max_count", namespace),
-                pool.max_size() as f64,
-            );
-            if let Some(min) = pool.min_idle() {
-                metric.gauge(
-                    K::new_key("thread_pool.min_idle_count", namespace),
-                    min as f64,
-                );
+            let stats = PoolStats::new(&pool);
+            metric.gauge(K::new_key(POOL_MAX_COUNT, namespace), stats.max_size as f64);
+            if let Some(min) = stats.min_idle {
+                metric.gauge(K::new_key(POOL_MIN_IDLE_COUNT, namespace), min as f64);
             }
             metric.add_listen_state(move |env| {
-                let state = pool.state();
-                env.gauge(
-                    K::new_key("thread_pool.idle_count", namespace),
-                    state.idle_connections as f64,
-                );
+                let stats = PoolStats::new(&pool);
+                env.gauge(K::new_key(POOL_IDLE_COUNT, namespace), stats.idle as f64);
                 env.gauge(
-                    K::new_key("thread_pool.active_count", namespace),
-                    state.connections as f64,
+                    K::new_key(POOL_ACTIVE_COUNT, namespace),
+                    stats.active as f64,
                 );
                 Ok(())
             });
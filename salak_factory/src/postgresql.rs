@@ -18,7 +18,7 @@ use std::{
 };
 
 use crate::{
-    pool::{ManagedConnection, PoolConfig, PoolCustomizer},
+    pool::{ManagedConnection, PoolConfig, PoolCustomizer, PoolStats},
     WrapEnum,
 };
 
@@ -48,6 +48,7 @@ use crate::{
 /// |postgresql.pool.idle_timeout|false|${pool.idle_timeout:}|
 /// |postgresql.pool.connection_timeout|false|${pool.connection_timeout:5s}|
 /// |postgresql.pool.wait_for_init|false|${pool.wait_for_init:false}|
+/// |postgresql.pool.warmup_connections|false|${pool.warmup_connections:}|
 #[cfg_attr(docsrs, doc(cfg(feature = "postgresql")))]
 #[derive(FromEnvironment, Debug)]
 #[salak(prefix = "postgresql")]
@@ -165,6 +166,13 @@ impl Deref for PostgresPool {
     }
 }
 
+impl PostgresPool {
+    /// Get pool statistics.
+    pub fn stats(&self) -> PoolStats {
+        PoolStats::new(&self.0)
+    }
+}
+
 /// Postgres Customizer.
 #[allow(missing_debug_implementations)]
 #[cfg_attr(docsrs, doc(cfg(feature = "postgresql")))]
@@ -246,6 +254,12 @@ impl Resource for PostgresPool {
             Self,
         >(pool, factory)
     }
+
+    fn health_check(&self) -> Result<(), PropertyError> {
+        let mut client = self.get()?;
+        client.simple_query("SELECT 1")?;
+        Ok(())
+    }
 }
 
 impl PostgresCustomizer {
@@ -1,6 +1,6 @@
 //! Redis cluster connection pool resource.
 use crate::pool::ManagedConnection;
-use crate::pool::{PoolConfig, PoolCustomizer};
+use crate::pool::{PoolConfig, PoolCustomizer, PoolStats};
 use ::redis::cluster::*;
 use ::redis::*;
 use r2d2::{ManageConnection, Pool};
@@ -29,6 +29,7 @@ use std::{str::FromStr, time::Duration};
 /// |redis.cluster.pool.idle_timeout|false|${pool.idle_timeout:}|
 /// |redis.cluster.pool.connection_timeout|false|${pool.connection_timeout:5s}|
 /// |redis.cluster.pool.wait_for_init|false|${pool.wait_for_init:false}|
+/// |redis.cluster.pool.warmup_connections|false|${pool.warmup_connections:}|
 #[cfg_attr(docsrs, doc(cfg(feature = "redis_cluster")))]
 #[derive(FromEnvironment, Debug)]
 #[salak(prefix = "redis.cluster")]
@@ -91,6 +92,13 @@ impl Deref for RedisClusterPool {
     }
 }
 
+impl RedisClusterPool {
+    /// Get pool statistics.
+    pub fn stats(&self) -> PoolStats {
+        PoolStats::new(&self.0)
+    }
+}
+
 impl Resource for RedisClusterPool {
     type Config = RedisClusterConfig;
     type Customizer = PoolCustomizer<RedisClusterConnectionManager>;
@@ -144,6 +152,12 @@ impl Resource for RedisClusterPool {
             Self,
         >(pool, factory)
     }
+
+    fn health_check(&self) -> Result<(), PropertyError> {
+        let mut conn = self.get()?;
+        cmd("PING").query::<()>(&mut *conn)?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,5 @@
 //! Single node redis configuratino.
-use crate::pool::{ManagedConnection, PoolConfig, PoolCustomizer};
+use crate::pool::{ManagedConnection, PoolConfig, PoolCustomizer, PoolStats};
 use ::redis::*;
 use r2d2::{ManageConnection, Pool};
 use salak::*;
@@ -30,6 +30,7 @@ use std::{ops::Deref, sync::Arc, time::Duration};
 /// |redis.pool.idle_timeout|false|${pool.idle_timeout:}|
 /// |redis.pool.connection_timeout|false|${pool.connection_timeout:5s}|
 /// |redis.pool.wait_for_init|false|${pool.wait_for_init:false}|
+/// |redis.pool.warmup_connections|false|${pool.warmup_connections:}|
 #[cfg_attr(docsrs, doc(cfg(feature = "redis_default")))]
 #[derive(FromEnvironment, Debug)]
 #[salak(prefix = "redis")]
@@ -107,6 +108,13 @@ impl Deref for RedisPool {
     }
 }
 
+impl RedisPool {
+    /// Get pool statistics.
+    pub fn stats(&self) -> PoolStats {
+        PoolStats::new(&self.0)
+    }
+}
+
 impl Resource for RedisPool {
     type Config = RedisConfig;
     type Customizer = PoolCustomizer<RedisConnectionManager>;
@@ -166,6 +174,12 @@ impl Resource for RedisPool {
             Self,
         >(pool, factory)
     }
+
+    fn health_check(&self) -> Result<(), PropertyError> {
+        let mut conn = self.get()?;
+        cmd("PING").query::<()>(&mut *conn)?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -177,4 +191,28 @@ mod tests {
         let pool = env.init_resource::<RedisPool>();
         assert_eq!(true, pool.is_ok());
     }
+
+    #[test]
+    fn redis_warmup_tests() {
+        let env = Salak::builder()
+            .set("redis.pool.max_size", "2")
+            .set("redis.pool.warmup_connections", "3")
+            .build()
+            .unwrap();
+        let pool = env.init_resource::<RedisPool>();
+        assert_eq!(true, pool.is_err());
+    }
+
+    #[test]
+    fn redis_stats_tests() {
+        let env = Salak::builder()
+            .set("redis.pool.max_size", "3")
+            .set("redis.pool.min_idle", "0")
+            .build()
+            .unwrap();
+        let stats = env.init_resource::<RedisPool>().unwrap().stats();
+        assert_eq!(3, stats.max_size);
+        assert_eq!(Some(0), stats.min_idle);
+        assert_eq!(true, stats.idle <= stats.active);
+    }
 }
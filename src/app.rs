@@ -1,11 +1,11 @@
-use crate::*;
-use parking_lot::Mutex;
+use crate::{err::SendError, *};
+use parking_lot::{Mutex, RwLock};
 use std::{
     any::{Any, TypeId},
     cmp::Ordering,
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     sync::Arc,
-    thread::spawn,
+    thread::{scope, Builder, ThreadId},
 };
 
 #[cfg_attr(docsrs, doc(cfg(feature = "app")))]
@@ -63,6 +63,20 @@ pub trait Resource: Sized {
     fn order() -> Ordered {
         PRIORITY_NORMAL
     }
+
+    /// Check whether current resource is healthy, default is always healthy.
+    /// Resources such as connection pools may override this function to
+    /// validate their connections.
+    fn health_check(&self) -> Void {
+        Ok(())
+    }
+
+    /// Whether current resource can be recreated when reloading. If true, [`Environment::reload()`]
+    /// will recreate the resource when its configuration properties change, and replace the cached
+    /// one, the customizer is only applied at the first creation. Default is false.
+    fn reloadable() -> bool {
+        false
+    }
 }
 
 /// Resource priority.
@@ -70,6 +84,16 @@ pub trait Resource: Sized {
 pub struct Ordered(i32);
 
 impl Ordered {
+    /// Create priority with exact rank, smaller rank will be initialized earlier.
+    pub const fn exact(rank: i32) -> Self {
+        Ordered(rank)
+    }
+
+    /// Get rank of current priority.
+    pub const fn raw(self) -> i32 {
+        self.0
+    }
+
     /// Current resouce should init after then target resouce.
     pub fn after<R: Resource>(self) -> Self {
         let r = R::order();
@@ -130,6 +154,12 @@ impl FactoryContext<'_> {
         self.namespace
     }
 
+    /// Get the underlying environment, which can be used for
+    /// getting properties out of resource configuration.
+    pub fn env(&self) -> &Salak {
+        self.fac
+    }
+
     /// Get resource with default namespace. The resource will be
     /// initialized if it does not exist yet.
     pub fn get_resource<R: Resource + Send + Sync + Any>(&self) -> Res<Arc<R>> {
@@ -150,6 +180,21 @@ impl FactoryContext<'_> {
         );
         self.fac.res.get_ref(namespace, self.fac, false)
     }
+    /// Get resource with default namespace only if it is already initialized,
+    /// otherwise [`PropertyError::ResourceNotFound`] is returned.
+    pub fn try_get_resource<R: Resource + Send + Sync + Any>(&self) -> Res<Arc<R>> {
+        self.try_get_resource_by_namespace("")
+    }
+
+    /// Get resource with namespace only if it is already initialized,
+    /// otherwise [`PropertyError::ResourceNotFound`] is returned.
+    pub fn try_get_resource_by_namespace<R: Resource + Send + Sync + Any>(
+        &self,
+        namespace: &'static str,
+    ) -> Res<Arc<R>> {
+        self.fac.res.get_ref(namespace, self.fac, true)
+    }
+
     #[inline]
     /// Get optional resource.
     pub fn get_optional_resource<R: Resource + Send + Sync + Any>(&self) -> Res<Option<Arc<R>>> {
@@ -180,26 +225,67 @@ impl FactoryContext<'_> {
 struct Task(
     Option<
         Box<
-            dyn FnOnce(&Salak) -> Res<Box<dyn FnOnce() + Send + Sync + 'static>>
+            dyn FnOnce(&Salak) -> Res<Box<dyn FnOnce() -> Void + Send + Sync + 'static>>
                 + Send
                 + Sync
                 + 'static,
         >,
     >,
+    Option<String>,
 );
 
 impl Task {
     fn new<R: Resource + Send + Sync + 'static>(
         namespace: &'static str,
+        name: Option<String>,
         task: impl Fn(Arc<R>) -> Void + Send + Sync + 'static,
     ) -> Self {
-        Task(Some(Box::new(move |env: &Salak| {
-            let res = env.res.get_ref::<R>(namespace, env, true)?;
-            Ok(Box::new(move || (task)(res).unwrap()))
-        })))
+        Task(
+            Some(Box::new(move |env: &Salak| {
+                let res = env.res.get_ref::<R>(namespace, env, true)?;
+                Ok(Box::new(move || (task)(res)))
+            })),
+            name,
+        )
     }
 }
 
+/// Failures of tasks reported by [`Factory::run()`], get it by
+/// `err.parse_cause()?.downcast_ref::<TaskError>()`.
+/// Failure of single unnamed task is reported as the original error.
+#[cfg_attr(docsrs, doc(cfg(feature = "app")))]
+#[derive(Debug)]
+pub enum TaskError {
+    /// Task with optional name returned the error, or failed to start.
+    Failed(Option<String>, PropertyError),
+    /// Task with optional name panicked with the message.
+    Panicked(Option<String>, String),
+    /// Multiple tasks failed.
+    Multiple(Vec<TaskError>),
+}
+
+impl std::fmt::Display for TaskError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let task = |name: &Option<String>| match name {
+            Some(name) => format!("task '{}'", name),
+            _ => "task".to_string(),
+        };
+        match self {
+            TaskError::Failed(name, e) => write!(f, "{} failed: {}", task(name), e),
+            TaskError::Panicked(name, msg) => write!(f, "{} panicked: {}", task(name), msg),
+            TaskError::Multiple(errs) => {
+                write!(f, "{} tasks failed", errs.len())?;
+                for e in errs {
+                    write!(f, "; {}", e)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for TaskError {}
+
 /// Register dependent resources under same namespace.
 ///
 /// Only relavent resources can be registered by current
@@ -218,11 +304,34 @@ impl FactoryBuilder<'_> {
         &mut self,
         task: impl Fn(Arc<R>) -> Void + Send + Sync + 'static,
     ) -> Void {
-        let task = Task::new(self.namespace, task);
+        let task = Task::new(self.namespace, None, task);
         self.builder.1.push(task);
         Ok(())
     }
 
+    /// Submit remote task with name, which is used as thread name, and
+    /// reported by [`Salak::run()`] if the task fails.
+    pub fn submit_named<R: Resource + Send + Sync + Any>(
+        &mut self,
+        name: &str,
+        task: impl Fn(Arc<R>) -> Void + Send + Sync + 'static,
+    ) -> Void {
+        let task = Task::new(self.namespace, Some(name.to_string()), task);
+        self.builder.1.push(task);
+        Ok(())
+    }
+
+    /// Register dependent resource once per sub key under `list_key`, like
+    /// [`SalakBuilder::register_resources_from()`]. Sub keys are discovered when building,
+    /// so resources can be registered data-driven from configuration.
+    pub fn register_resources_from<R: Resource + Send + Sync + Any>(
+        &mut self,
+        list_key: &str,
+    ) -> Void {
+        self.builder.discover::<R>(list_key);
+        Ok(())
+    }
+
     /// Register dependent resource under current namespace.
     pub fn register_resource<R: Resource + Send + Sync + Any>(&mut self) -> Void {
         self.builder
@@ -322,7 +431,13 @@ pub trait Factory: Environment {
        self.init_resource::<S>()
     }
 
-    /// Run the resource.
+    /// Check health of all initialized resources, grouped by resource
+    /// type name and namespace.
+    fn health(&self) -> BTreeMap<(&'static str, &'static str), Void>;
+
+    /// Run submitted tasks and wait for them to finish. Errors and panics of
+    /// tasks are reported as [`TaskError`], multiple failures are aggregated into one error.
+    /// If any task fails to start, tasks already started are still waited for.
     fn run(&mut self) -> Void;
 }
 
@@ -356,6 +471,10 @@ macro_rules! impl_container {
             fn order() -> Ordered {
                 T::order()
             }
+
+            fn reloadable() -> bool {
+                T::reloadable()
+            }
         }
     )+};
 }
@@ -389,14 +508,68 @@ impl<T: Resource> Resource for Option<T> {
     fn order() -> Ordered {
         T::order()
     }
+
+    fn reloadable() -> bool {
+        T::reloadable()
+    }
+
+    fn health_check(&self) -> Void {
+        match self {
+            Some(v) => v.health_check(),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "app")))]
+/// Handle of [`Salak`] for reading configurations ad hoc, which can be injected into
+/// services like other resources. Register it by
+/// [`SalakBuilder::register_default_resource()`].
+///
+/// The handle shares sources with [`Salak`], so it follows [`Salak::set_runtime()`] and
+/// reloading, and [`crate::wrapper::IORef`]s required by it are updated by reloading [`Salak`].
+#[allow(missing_debug_implementations)]
+pub struct EnvHandle {
+    reg: Arc<RwLock<Arc<PropertyRegistryInternal<'static>>>>,
+    ior: Arc<Mutex<Vec<Box<dyn IORefT + Send>>>>,
+}
+
+impl Resource for EnvHandle {
+    type Config = ();
+    type Customizer = ();
+
+    fn create(
+        _: Self::Config,
+        factory: &FactoryContext<'_>,
+        _: impl FnOnce(&mut Self::Customizer, &Self::Config) -> Void,
+    ) -> Res<Self> {
+        let env = factory.env();
+        Ok(EnvHandle {
+            reg: env.live.clone(),
+            ior: env.ior.clone(),
+        })
+    }
+}
+
+impl Environment for EnvHandle {
+    #[inline]
+    fn require<T: FromEnvironment>(&self, key: &str) -> Res<T> {
+        let reg = self.reg.read().clone();
+        reg.require(key, &self.ior)
+    }
+
+    /// Handle can not reload configurations, reload [`Salak`] instead, always returns `false`.
+    fn reload(&self) -> Res<bool> {
+        Ok(false)
+    }
 }
 
-struct Init(Box<dyn FnOnce(&Salak, &Mutex<ResVal>) -> Void + Send>);
+struct Init(Box<dyn FnOnce(&Salak, &Mutex<ResVal>, Option<&Reload>) -> Void + Send>);
 
 impl<R: Resource + Send + Sync + 'static> ResourceBuilder<R> {
     #[inline]
     fn into_init(self) -> Init {
-        Init(Box::new(move |env, val| {
+        Init(Box::new(move |env, val, reload| {
             #[cfg(feature = "log")]
             log::info!(
                 "init resource ({}) at namespace [{}].",
@@ -404,6 +577,13 @@ impl<R: Resource + Send + Sync + 'static> ResourceBuilder<R> {
                 self.namespace
             );
             let namespace = self.namespace;
+            let last = match reload {
+                Some(_) => {
+                    let prefix = env.resource_prefix::<R>(self.config_prefix, namespace)?;
+                    Some(env.reg.snapshot(&prefix))
+                }
+                _ => None,
+            };
             let context = FactoryContext {
                 fac: env,
                 namespace,
@@ -411,6 +591,9 @@ impl<R: Resource + Send + Sync + 'static> ResourceBuilder<R> {
             let res = Arc::new(env.do_init_resource_with_builder::<R>(&context, self)?);
             R::post_initialized_and_registered(&res, &context)?;
             *val.lock() = Some(res);
+            if let Some(reload) = reload {
+                *reload.last.lock() = last;
+            }
             Ok(())
         }))
     }
@@ -418,12 +601,90 @@ impl<R: Resource + Send + Sync + 'static> ResourceBuilder<R> {
 
 type ResVal = Option<Arc<dyn Any + Send + Sync>>;
 
+/// Type erased [`Resource::health_check()`].
+struct Health(&'static str, fn(&(dyn Any + Send + Sync)) -> Void);
+
+impl Health {
+    fn new<R: Resource + 'static>() -> Self {
+        Health(std::any::type_name::<R>(), |v| {
+            match v.downcast_ref::<R>() {
+                Some(r) => r.health_check(),
+                _ => Ok(()),
+            }
+        })
+    }
+}
+
+/// Type erased resource recreation when reloading, with the last configuration
+/// properties of the resource.
+struct Reload {
+    /// Recreate the resource if its configuration properties changed.
+    recreate:
+        fn(&Reload, &Mutex<ResVal>, &Salak, &PropertyRegistryInternal<'_>, &'static str) -> Void,
+    /// Configuration properties the resource was last created with.
+    last: Mutex<Option<BTreeMap<String, String>>>,
+    /// Prefix of configuration properties set by [`ResourceBuilder::config_prefix()`].
+    config_prefix: Option<&'static str>,
+}
+
+impl Reload {
+    fn new<R: Resource + Send + Sync + 'static>(
+        config_prefix: Option<&'static str>,
+    ) -> Option<Self> {
+        if !R::reloadable() {
+            return None;
+        }
+        Some(Reload {
+            recreate: |reload, val, env, registry, namespace| {
+                if val.lock().is_none() {
+                    return Ok(());
+                }
+                let prefix = env.resource_prefix::<R>(reload.config_prefix, namespace)?;
+                let current = registry.snapshot(&prefix);
+                let mut last = reload.last.lock();
+                if last.as_ref() == Some(&current) {
+                    return Ok(());
+                }
+                #[cfg(feature = "log")]
+                log::info!(
+                    "Reload resource ({}) at namespace [{}].",
+                    std::any::type_name::<R>(),
+                    namespace
+                );
+                let config = registry.require::<R::Config>(&prefix, &env.ior)?;
+                let context = FactoryContext {
+                    fac: env,
+                    namespace,
+                };
+                let res = Arc::new(R::create(config, &context, |_, _| Ok(()))?);
+                R::post_initialized_and_registered(&res, &context)?;
+                *val.lock() = Some(res);
+                *last = Some(current);
+                Ok(())
+            },
+            last: Mutex::new(None),
+            config_prefix,
+        })
+    }
+}
+
 /// ResourceHolder is [`Sync`] and [`Send`] only when value in box is [`Send`].
-struct ResourceHolder(Mutex<ResVal>, Mutex<Option<Init>>, Ordered);
+///
+/// The initializer is guarded by a lock, so other threads wait until initialization
+/// completes. Recursive dependencies, even across threads, are detected by [`InitGraph`]
+/// before waiting.
+struct ResourceHolder {
+    value: Mutex<ResVal>,
+    init: Mutex<Option<Init>>,
+    order: Ordered,
+    id: TypeId,
+    health: Health,
+    reload: Option<Reload>,
+}
 
 impl PartialEq for ResourceHolder {
     fn eq(&self, r: &ResourceHolder) -> bool {
-        self.2 == r.2
+        self.order == r.order
     }
 }
 
@@ -431,31 +692,102 @@ impl Eq for ResourceHolder {}
 
 impl PartialOrd for ResourceHolder {
     fn partial_cmp(&self, r: &ResourceHolder) -> Option<Ordering> {
-        self.2.partial_cmp(&r.2)
+        self.order.partial_cmp(&r.order)
     }
 }
 impl Ord for ResourceHolder {
     fn cmp(&self, r: &Self) -> Ordering {
-        self.2.cmp(&r.2)
+        self.order.cmp(&r.order)
+    }
+}
+
+/// Resource identified by type and namespace.
+type ResKey = (TypeId, &'static str);
+
+/// Threads initializing resources and resources threads are waiting for.
+#[derive(Default)]
+struct InitGraph {
+    owners: HashMap<ResKey, ThreadId>,
+    waiting: HashMap<ThreadId, ResKey>,
+}
+
+impl InitGraph {
+    /// Mark current thread waiting for `key`, fails if the owner of `key` is waiting for
+    /// resources initialized by current thread, which never completes.
+    fn wait(&mut self, key: ResKey, current: ThreadId) -> bool {
+        let mut next = key;
+        while let Some(owner) = self.owners.get(&next) {
+            if *owner == current {
+                return false;
+            }
+            match self.waiting.get(owner) {
+                Some(k) => next = *k,
+                _ => break,
+            }
+        }
+        self.waiting.insert(current, key);
+        true
+    }
+}
+
+/// Release resource in [`InitGraph`] when initialization completes or panics.
+struct InitGuard<'a>(&'a Mutex<InitGraph>, ResKey);
+
+impl Drop for InitGuard<'_> {
+    fn drop(&mut self) {
+        self.0.lock().owners.remove(&self.1);
     }
 }
 
 impl ResourceHolder {
     fn new<R: Resource + Send + Sync + 'static>(builder: ResourceBuilder<R>) -> Self {
         let order = builder.order;
-        Self(
-            Mutex::new(None),
-            Mutex::new(Some(builder.into_init())),
+        let config_prefix = builder.config_prefix;
+        Self {
+            value: Mutex::new(None),
+            init: Mutex::new(Some(builder.into_init())),
             order,
-        )
+            id: TypeId::of::<R>(),
+            health: Health::new::<R>(),
+            reload: Reload::new::<R>(config_prefix),
+        }
+    }
+
+    /// Check health without holding the value lock, health check may take long for I/O.
+    fn health(&self) -> Option<Void> {
+        let value = self.value.lock().clone();
+        value.map(|v| (self.health.1)(v.as_ref()))
     }
 
     #[inline]
-    fn init(&self, env: &Salak) -> Void {
-        let mut guard = self.1.lock();
-        if let Some(b) = guard.take() {
-            drop(guard);
-            return (b.0)(env, &self.0);
+    fn init(&self, env: &Salak, namespace: &'static str) -> Void {
+        let key = (self.id, namespace);
+        let current = std::thread::current().id();
+        if !env.res.4.lock().wait(key, current) {
+            return Err(PropertyError::ResourceRecursive(namespace, self.health.0));
+        }
+        let mut guard = self.init.lock();
+        let init = guard.take();
+        let _release = {
+            let mut graph = env.res.4.lock();
+            graph.waiting.remove(&current);
+            if init.is_none() {
+                return Ok(());
+            }
+            graph.owners.insert(key, current);
+            InitGuard(&env.res.4, key)
+        };
+        match init {
+            Some(b) => (b.0)(env, &self.value, self.reload.as_ref()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Initialize resource and check whether it is available.
+    fn try_init(&self, env: &Salak, namespace: &'static str) -> Void {
+        self.init(env, namespace)?;
+        if self.value.lock().is_none() {
+            return Err(PropertyError::ResourceNotFound(namespace, self.health.0));
         }
         Ok(())
     }
@@ -466,7 +798,7 @@ impl ResourceHolder {
         namespace: &'static str,
         query_only: bool,
     ) -> Res<Arc<R>> {
-        let guard = self.0.lock();
+        let guard = self.value.lock();
         if let Some(arc) = guard.as_ref() {
             if let Ok(v) = arc.clone().downcast::<R>() {
                 return Ok(v);
@@ -484,7 +816,7 @@ impl ResourceHolder {
                 std::any::type_name::<R>(),
             ));
         }
-        self.init(env)?;
+        self.init(env, namespace)?;
         match self.get_or_init(env, namespace, true) {
             Err(PropertyError::ResourceNotFound(a, b)) => {
                 Err(PropertyError::ResourceRecursive(a, b))
@@ -494,29 +826,104 @@ impl ResourceHolder {
     }
 }
 
+/// Register resource at discovered namespace.
+type RegisterNamespace = fn(&mut ResourceRegistry, &'static str) -> Void;
+
 pub(crate) struct ResourceRegistry(
     BTreeMap<TypeId, BTreeMap<&'static str, ResourceHolder>>,
     Vec<Task>,
+    /// Resources registered on first use by [`Salak::get_or_init_resource()`].
+    Mutex<BTreeMap<TypeId, Arc<ResourceHolder>>>,
+    /// Resources registered per sub key of list key, which are discovered when building.
+    VecDeque<(String, RegisterNamespace)>,
+    /// Resources being initialized, used to detect recursive dependencies across threads.
+    Mutex<InitGraph>,
 );
 
 impl ResourceRegistry {
     pub(crate) fn new() -> Self {
-        Self(BTreeMap::new(), vec![])
+        Self(
+            BTreeMap::new(),
+            vec![],
+            Mutex::new(BTreeMap::new()),
+            VecDeque::new(),
+            Mutex::new(InitGraph::default()),
+        )
     }
 
-    pub(crate) fn initialize(&self, env: &Salak) -> Void {
-        let mut v = BTreeSet::new();
+    pub(crate) fn discover<R: Resource + Send + Sync + Any>(&mut self, list_key: &str) {
+        self.3
+            .push_back((list_key.to_string(), Self::register_namespace::<R>));
+    }
+
+    pub(crate) fn next_discovery(&mut self) -> Option<(String, RegisterNamespace)> {
+        self.3.pop_front()
+    }
+
+    /// Intern discovered namespace, each namespace is leaked only once no matter
+    /// how many envs are built.
+    pub(crate) fn intern(namespace: String) -> &'static str {
+        lazy_static::lazy_static! {
+            static ref NAMESPACES: Mutex<HashSet<&'static str>> = Mutex::new(HashSet::new());
+        }
+        let mut guard = NAMESPACES.lock();
+        if let Some(v) = guard.get(namespace.as_str()) {
+            return v;
+        }
+        let v: &'static str = Box::leak(namespace.into_boxed_str());
+        guard.insert(v);
+        v
+    }
+
+    pub(crate) fn initialize(&self, env: &Salak, parallel: bool) -> Void {
+        let mut v: BTreeMap<Ordered, Vec<(&'static str, &ResourceHolder)>> = BTreeMap::new();
         for x in self.0.values() {
-            for r in x.values() {
-                v.insert(r);
+            for (namespace, r) in x {
+                v.entry(r.order).or_default().push((namespace, r));
             }
         }
-        for r in v {
-            r.init(env)?;
+        for group in v.values() {
+            if parallel && group.len() > 1 {
+                scope(|s| -> Void {
+                    let handles: Vec<_> = group
+                        .iter()
+                        .map(|(namespace, r)| {
+                            s.spawn(move || r.init(env, namespace).map_err(SendError::from))
+                        })
+                        .collect();
+                    for h in handles {
+                        match h.join() {
+                            Ok(v) => v.map_err(SendError::into_error)?,
+                            Err(_) => {
+                                return Err(PropertyError::parse_fail("resource init panicked"))
+                            }
+                        }
+                    }
+                    Ok(())
+                })?;
+            } else {
+                for (namespace, r) in group {
+                    r.init(env, namespace)?;
+                }
+            }
         }
         Ok(())
     }
 
+    /// Initialize all resources by order, failures will not stop initializing others.
+    fn initialize_all(&self, env: &Salak) -> BTreeMap<(&'static str, &'static str), Void> {
+        let mut v: Vec<(&'static str, &ResourceHolder)> = vec![];
+        for x in self.0.values() {
+            for (namespace, r) in x {
+                v.push((namespace, r));
+            }
+        }
+        v.sort_by_key(|(_, r)| r.order);
+        v.into_iter()
+            .map(|(namespace, r)| ((r.health.0, namespace), r.try_init(env, namespace)))
+            .collect()
+    }
+
     #[inline]
     pub(crate) fn register<R: Resource + Send + Sync + Any>(
         &mut self,
@@ -547,6 +954,13 @@ impl ResourceRegistry {
         })
     }
 
+    fn register_namespace<R: Resource + Send + Sync + Any>(
+        &mut self,
+        namespace: &'static str,
+    ) -> Void {
+        self.register::<R>(ResourceBuilder::new(namespace))
+    }
+
     #[inline]
     fn get_ref<R: Resource + Send + Sync + Any>(
         &self,
@@ -581,21 +995,120 @@ impl ResourceRegistry {
         }
         Ok(r)
     }
+
+    /// Get registered resource with default namespace, or register it on first use.
+    fn get_or_register<R: Resource + Send + Sync + Any>(&self, env: &Salak) -> Res<Arc<R>> {
+        if let Some(v) = self.0.get(&TypeId::of::<R>()).and_then(|map| map.get("")) {
+            return v.get_or_init(env, "", false);
+        }
+        let holder = self
+            .2
+            .lock()
+            .entry(TypeId::of::<R>())
+            .or_insert_with(|| {
+                #[cfg(feature = "log")]
+                log::info!(
+                    "Register resource ({}) at namespace [] on first use.",
+                    std::any::type_name::<R>()
+                );
+                Arc::new(ResourceHolder::new(ResourceBuilder::<R>::default()))
+            })
+            .clone();
+        holder.get_or_init(env, "", false)
+    }
+
+    pub(crate) fn reload(&self, env: &Salak, registry: &PropertyRegistryInternal<'_>) -> Void {
+        for map in self.0.values() {
+            for (namespace, v) in map {
+                if let Some(reload) = &v.reload {
+                    (reload.recreate)(reload, &v.value, env, registry, namespace)?;
+                }
+            }
+        }
+        let lazy: Vec<Arc<ResourceHolder>> = self.2.lock().values().cloned().collect();
+        for v in lazy {
+            if let Some(reload) = &v.reload {
+                (reload.recreate)(reload, &v.value, env, registry, "")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn health(&self) -> BTreeMap<(&'static str, &'static str), Void> {
+        let mut r = BTreeMap::new();
+        for map in self.0.values() {
+            for (namespace, v) in map {
+                if let Some(h) = v.health() {
+                    r.insert((v.health.0, *namespace), h);
+                }
+            }
+        }
+        for v in self.2.lock().values() {
+            if let Some(h) = v.health() {
+                r.entry((v.health.0, "")).or_insert(h);
+            }
+        }
+        r
+    }
 }
 
 impl Salak {
+    #[cfg_attr(docsrs, doc(cfg(feature = "app")))]
+    /// Initialize all registered resources, grouped by resource type name and namespace.
+    /// Failure of one resource will not stop initializing others. Resources failed before
+    /// will be reported as [`PropertyError::ResourceNotFound`].
+    pub fn init_all_resources(&self) -> BTreeMap<(&'static str, &'static str), Void> {
+        self.res.initialize_all(self)
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "app")))]
+    /// Get resource [`Arc<R>`] with default namespace, initializing it on first call.
+    /// Unlike [`Factory::get_resource()`], which fails if the resource is not registered,
+    /// an unregistered resource is registered with default [`ResourceBuilder`] and cached,
+    /// so later calls return the same [`Arc<R>`]. Dependent resources are not registered.
+    pub fn get_or_init_resource<R: Resource + Send + Sync + Any>(&self) -> Res<Arc<R>> {
+        self.res.get_or_register(self)
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "app")))]
+    /// Namespaces where resource `R` is registered, sorted. It tells why
+    /// [`PropertyError::ResourceNotFound`] is returned: empty if the resource type is
+    /// never registered, otherwise it is not registered under the requested namespace.
+    pub fn resource_namespaces<R: Resource + Send + Sync + Any>(&self) -> Vec<&'static str> {
+        self.res
+            .0
+            .get(&TypeId::of::<R>())
+            .map(|map| map.keys().copied().collect())
+            .unwrap_or_default()
+    }
+
     fn do_init_resource_with_builder<R: Resource>(
         &self,
         context: &FactoryContext<'_>,
         builder: ResourceBuilder<R>,
     ) -> Result<R, PropertyError> {
-        let config = if builder.namespace.is_empty() {
-            self.require::<R::Config>(<R::Config>::prefix())
-        } else {
-            self.require::<R::Config>(&format!("{}.{}", <R::Config>::prefix(), builder.namespace))
-        }?;
+        let prefix = self.resource_prefix::<R>(builder.config_prefix, builder.namespace)?;
+        let config = self.require::<R::Config>(&prefix)?;
         R::create(config, &context, builder.customizer)
     }
+
+    /// Resolve configuration prefix of resource, `config_prefix` overrides
+    /// [`PrefixedFromEnvironment::prefix()`] of config.
+    fn resource_prefix<R: Resource>(
+        &self,
+        config_prefix: Option<&'static str>,
+        namespace: &str,
+    ) -> Res<String> {
+        let prefix = match config_prefix {
+            Some(prefix) => self.resolve_placeholder(prefix)?,
+            None => <R::Config>::prefix_resolved(self)?,
+        };
+        if namespace.is_empty() {
+            Ok(prefix)
+        } else {
+            Ok(format!("{}.{}", prefix, namespace))
+        }
+    }
 }
 
 #[cfg_attr(docsrs, doc(cfg(feature = "app")))]
@@ -617,17 +1130,59 @@ impl Factory for Salak {
         self.do_init_resource_with_builder(&context, builder)
     }
 
+    #[inline]
+    fn health(&self) -> BTreeMap<(&'static str, &'static str), Void> {
+        self.res.health()
+    }
+
     fn run(&mut self) -> Void {
         let mut join = vec![];
-        for mut task in std::mem::replace(&mut self.res.1, vec![]) {
+        let mut errs = vec![];
+        for mut task in std::mem::take(&mut self.res.1) {
             if let Some(v) = task.0.take() {
-                join.push(spawn((v)(self)?));
+                let mut builder = Builder::new();
+                if let Some(name) = &task.1 {
+                    builder = builder.name(name.clone());
+                }
+                let spawn = |f: Box<dyn FnOnce() -> Void + Send + Sync>| {
+                    Ok(builder.spawn(move || f().map_err(SendError::from))?)
+                };
+                match (v)(self).and_then(spawn) {
+                    Ok(handle) => join.push((task.1, handle)),
+                    Err(e) => {
+                        // Tasks already spawned are still joined.
+                        errs.push(TaskError::Failed(task.1, e));
+                        break;
+                    }
+                }
             }
         }
-        for join in join {
-            let _ = join.join();
+        for (name, handle) in join {
+            match handle.join() {
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => errs.push(TaskError::Failed(name, e.into_error())),
+                Err(e) => errs.push(TaskError::Panicked(
+                    name,
+                    e.downcast_ref::<&str>()
+                        .copied()
+                        .or_else(|| e.downcast_ref::<String>().map(|v| v.as_str()))
+                        .unwrap_or("unknown")
+                        .to_string(),
+                )),
+            }
+        }
+        #[cfg(feature = "log")]
+        for e in &errs {
+            log::error!("Task failed: {}", e);
+        }
+        match errs.len() {
+            0 => Ok(()),
+            1 => match errs.remove(0) {
+                TaskError::Failed(None, e) => Err(e),
+                e => Err(e.into()),
+            },
+            _ => Err(TaskError::Multiple(errs).into()),
         }
-        Ok(())
     }
 }
 
@@ -636,6 +1191,7 @@ impl Factory for Salak {
 #[allow(missing_debug_implementations)]
 pub struct ResourceBuilder<R: Resource> {
     pub(crate) namespace: &'static str,
+    pub(crate) config_prefix: Option<&'static str>,
     order: Ordered,
     customizer: Box<dyn FnOnce(&mut R::Customizer, &R::Config) -> Void + Send>,
 }
@@ -652,6 +1208,7 @@ impl<R: Resource> ResourceBuilder<R> {
     pub fn new(namespace: &'static str) -> Self {
         Self {
             namespace,
+            config_prefix: None,
             order: R::order(),
             customizer: Box::new(|_, _| Ok(())),
         }
@@ -664,6 +1221,14 @@ impl<R: Resource> ResourceBuilder<R> {
         self
     }
 
+    #[inline]
+    /// Configure prefix of resource config, which overrides [`PrefixedFromEnvironment::prefix()`],
+    /// so config is parsed from `<config_prefix>.<namespace>`.
+    pub fn config_prefix(mut self, config_prefix: &'static str) -> Self {
+        self.config_prefix = Some(config_prefix);
+        self
+    }
+
     #[inline]
     /// Configure customize.
     pub fn customize(
@@ -711,6 +1276,7 @@ macro_rules! generate_service{
 mod tests {
 
     use crate::*;
+    use std::sync::atomic::{AtomicU64, AtomicUsize};
     #[test]
     fn app_test() {
         let env = Salak::builder().build().unwrap();
@@ -725,6 +1291,821 @@ mod tests {
         assert_eq!(true, v.is_ok());
     }
 
+    lazy_static::lazy_static! {
+        static ref INIT_ORDER: Mutex<Vec<&'static str>> = Mutex::new(vec![]);
+    }
+
+    macro_rules! impl_order_resource {
+        ($($x:ident => $o:expr),+) => {$(
+            struct $x;
+
+            impl Resource for $x {
+                type Config = ();
+                type Customizer = ();
+
+                fn create(
+                    _: Self::Config,
+                    factory: &FactoryContext<'_>,
+                    _: impl FnOnce(&mut Self::Customizer, &Self::Config) -> Void,
+                ) -> Res<Self> {
+                    if $o == PRIORITY_NORMAL {
+                        let _ = factory.get_resource::<Dep>()?;
+                    }
+                    INIT_ORDER.lock().push(stringify!($x));
+                    Ok($x)
+                }
+
+                fn order() -> Ordered {
+                    $o
+                }
+            }
+        )+};
+    }
+
+    impl_order_resource!(
+        High1 => PRIORITY_HIGH,
+        High2 => PRIORITY_HIGH,
+        Normal1 => PRIORITY_NORMAL,
+        Normal2 => PRIORITY_NORMAL,
+        Normal3 => PRIORITY_NORMAL,
+        Dep => PRIORITY_LOW,
+        Low => PRIORITY_LOW
+    );
+
+    #[test]
+    fn parallel_init_test() {
+        let env = Salak::builder()
+            .parallel_init(true)
+            .register_default_resource::<Low>()
+            .unwrap()
+            .register_default_resource::<Normal1>()
+            .unwrap()
+            .register_default_resource::<Normal2>()
+            .unwrap()
+            .register_default_resource::<Normal3>()
+            .unwrap()
+            .register_default_resource::<High1>()
+            .unwrap()
+            .register_default_resource::<High2>()
+            .unwrap()
+            .register_default_resource::<Dep>()
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(env.get_resource::<Normal1>().is_ok());
+        assert!(env.get_resource::<Normal2>().is_ok());
+        assert!(env.get_resource::<Normal3>().is_ok());
+        assert!(env.get_resource::<Low>().is_ok());
+        let order = INIT_ORDER.lock().clone();
+        assert_eq!(7, order.len());
+        let pos = |x: &str| order.iter().position(|v| *v == x).unwrap();
+        assert!(pos("High1") < 2 && pos("High2") < 2);
+        assert_eq!(2, pos("Dep"));
+        assert_eq!(6, pos("Low"));
+    }
+
+    /// Threads meet at rendezvous, fails after timeout if they never run concurrently.
+    struct Rendezvous(Mutex<usize>, parking_lot::Condvar);
+
+    impl Rendezvous {
+        fn meet(&self, n: usize) -> Void {
+            let mut count = self.0.lock();
+            *count += 1;
+            self.1.notify_all();
+            while *count < n {
+                if self
+                    .1
+                    .wait_for(&mut count, std::time::Duration::from_secs(5))
+                    .timed_out()
+                {
+                    return Err(PropertyError::parse_fail("resources not init concurrently"));
+                }
+            }
+            Ok(())
+        }
+    }
+
+    lazy_static::lazy_static! {
+        static ref MEET: Rendezvous = Rendezvous(Mutex::new(0), parking_lot::Condvar::new());
+        static ref CYCLE: Rendezvous = Rendezvous(Mutex::new(0), parking_lot::Condvar::new());
+        static ref HEALTH: Rendezvous = Rendezvous(Mutex::new(0), parking_lot::Condvar::new());
+    }
+
+    macro_rules! impl_meet_resource {
+        ($($x:ident => $meet:ident, $dep:ty),+) => {$(
+            struct $x;
+
+            impl Resource for $x {
+                type Config = ();
+                type Customizer = ();
+
+                fn create(
+                    _: Self::Config,
+                    factory: &FactoryContext<'_>,
+                    _: impl FnOnce(&mut Self::Customizer, &Self::Config) -> Void,
+                ) -> Res<Self> {
+                    $meet.meet(2)?;
+                    let _ = factory.get_resource::<$dep>()?;
+                    Ok($x)
+                }
+            }
+        )+};
+    }
+
+    impl_meet_resource!(
+        Meet1 => MEET, (),
+        Meet2 => MEET, (),
+        CycleA => CYCLE, CycleB,
+        CycleB => CYCLE, CycleA
+    );
+
+    #[test]
+    fn parallel_init_concurrent_test() {
+        let env = Salak::builder()
+            .parallel_init(true)
+            .register_default_resource::<()>()
+            .unwrap()
+            .register_default_resource::<Meet1>()
+            .unwrap()
+            .register_default_resource::<Meet2>()
+            .unwrap()
+            .build();
+        assert!(env.is_ok());
+    }
+
+    #[test]
+    fn parallel_init_cycle_test() {
+        let env = Salak::builder()
+            .parallel_init(true)
+            .register_default_resource::<CycleA>()
+            .unwrap()
+            .register_default_resource::<CycleB>()
+            .unwrap()
+            .build();
+        match env {
+            Err(PropertyError::ResourceRecursive(_, _)) => {}
+            Err(e) => panic!("unexpected error {:?}", e),
+            Ok(_) => panic!("cycle not detected"),
+        }
+    }
+
+    #[test]
+    fn ordered_test() {
+        assert_eq!(PRIORITY_NORMAL, Ordered::exact(0));
+        assert_eq!(0, PRIORITY_NORMAL.raw());
+        assert!(PRIORITY_HIGHEST < PRIORITY_HIGH);
+        assert!(PRIORITY_HIGH < Ordered::exact(-1));
+        assert!(Ordered::exact(-1) < PRIORITY_NORMAL);
+        assert!(PRIORITY_NORMAL < Ordered::exact(1));
+        assert!(Ordered::exact(1) < PRIORITY_LOW);
+        assert!(PRIORITY_LOW < PRIORITY_LOWEST);
+        assert_eq!(1, Ordered::exact(0).after::<()>().raw());
+        assert_eq!(-1, PRIORITY_NORMAL.before::<()>().raw());
+        assert_eq!(
+            PRIORITY_LOW.raw(),
+            Ordered::exact(i32::MAX).before::<Low>().raw()
+        );
+    }
+
+    struct Unhealthy;
+
+    impl Resource for Unhealthy {
+        type Config = ();
+        type Customizer = ();
+
+        fn create(
+            _: Self::Config,
+            _: &FactoryContext<'_>,
+            _: impl FnOnce(&mut Self::Customizer, &Self::Config) -> Void,
+        ) -> Res<Self> {
+            Ok(Unhealthy)
+        }
+
+        fn health_check(&self) -> Void {
+            Err(PropertyError::parse_fail("unhealthy"))
+        }
+    }
+
+    struct SlowHealth;
+
+    impl Resource for SlowHealth {
+        type Config = ();
+        type Customizer = ();
+
+        fn create(
+            _: Self::Config,
+            _: &FactoryContext<'_>,
+            _: impl FnOnce(&mut Self::Customizer, &Self::Config) -> Void,
+        ) -> Res<Self> {
+            Ok(SlowHealth)
+        }
+
+        /// Wait until the resource is got by another thread during checking.
+        fn health_check(&self) -> Void {
+            HEALTH.meet(2)?;
+            HEALTH.meet(4)
+        }
+    }
+
+    #[test]
+    fn health_unlocked_test() {
+        let env = Salak::builder()
+            .register_default_resource::<SlowHealth>()
+            .unwrap()
+            .build()
+            .unwrap();
+        std::thread::scope(|s| {
+            let health = s.spawn(|| {
+                env.health()
+                    .get(&("salak::app::tests::SlowHealth", ""))
+                    .map(|v| v.is_ok())
+            });
+            assert!(HEALTH.meet(2).is_ok());
+            assert!(env.get_resource::<SlowHealth>().is_ok());
+            assert!(HEALTH.meet(4).is_ok());
+            assert_eq!(Some(true), health.join().unwrap());
+        });
+    }
+
+    struct Broken;
+
+    impl Resource for Broken {
+        type Config = ();
+        type Customizer = ();
+
+        fn create(
+            _: Self::Config,
+            _: &FactoryContext<'_>,
+            _: impl FnOnce(&mut Self::Customizer, &Self::Config) -> Void,
+        ) -> Res<Self> {
+            Err(PropertyError::parse_fail("broken"))
+        }
+    }
+
+    #[test]
+    fn init_all_resources_test() {
+        let builder = || {
+            Salak::builder()
+                .register_default_resource::<()>()
+                .unwrap()
+                .register_resource::<Broken>(ResourceBuilder::new("a"))
+                .unwrap()
+                .register_resource::<Broken>(ResourceBuilder::new("b"))
+                .unwrap()
+                .register_default_resource::<Greeting>()
+                .unwrap()
+        };
+        assert!(builder().build().is_err());
+        let env = builder().defer_init(true).build().unwrap();
+        assert!(env.get_resource::<()>().is_err());
+        let report = env.init_all_resources();
+        assert_eq!(4, report.len());
+        let broken = std::any::type_name::<Broken>();
+        assert!(report[&("()", "")].is_ok());
+        assert!(report[&(broken, "a")].is_err());
+        assert!(report[&(broken, "b")].is_err());
+        assert!(matches!(
+            report[&(std::any::type_name::<Greeting>(), "")],
+            Err(PropertyError::NotFound(_))
+        ));
+        assert!(env.get_resource::<()>().is_ok());
+        assert!(matches!(
+            env.init_all_resources()[&(broken, "a")],
+            Err(PropertyError::ResourceNotFound("a", _))
+        ));
+    }
+
+    #[test]
+    fn health_test() {
+        let env = Salak::builder()
+            .register_default_resource::<()>()
+            .unwrap()
+            .register_resource::<Unhealthy>(ResourceBuilder::new("hello"))
+            .unwrap()
+            .build()
+            .unwrap();
+        let health = env.health();
+        assert_eq!(2, health.len());
+        assert!(health[&("()", "")].is_ok());
+        assert!(health[&(std::any::type_name::<Unhealthy>(), "hello")].is_err());
+    }
+
+    struct Probe {
+        present: bool,
+        on_demand: bool,
+    }
+
+    impl Resource for Probe {
+        type Config = ();
+        type Customizer = ();
+
+        fn create(
+            _: Self::Config,
+            factory: &FactoryContext<'_>,
+            _: impl FnOnce(&mut Self::Customizer, &Self::Config) -> Void,
+        ) -> Res<Self> {
+            let present = match factory.try_get_resource::<()>() {
+                Err(PropertyError::ResourceNotFound("", _)) => false,
+                v => v.is_ok(),
+            };
+            Ok(Probe {
+                present,
+                on_demand: factory.get_resource::<()>().is_ok(),
+            })
+        }
+    }
+
+    #[test]
+    fn try_get_resource_test() {
+        let env = Salak::builder()
+            .register_default_resource::<()>()
+            .unwrap()
+            .defer_init(true)
+            .build()
+            .unwrap();
+        let probe = env.init_resource::<Probe>().unwrap();
+        assert!(!probe.present);
+        assert!(probe.on_demand);
+        let probe = env.init_resource::<Probe>().unwrap();
+        assert!(probe.present);
+        assert!(probe.on_demand);
+    }
+
+    #[test]
+    fn resource_not_found_test() {
+        let env = Salak::builder()
+            .register_default_resource::<()>()
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(matches!(
+            env.get_resource::<Unhealthy>(),
+            Err(PropertyError::ResourceNotFound("", _))
+        ));
+        assert!(env.resource_namespaces::<Unhealthy>().is_empty());
+        assert!(matches!(
+            env.get_resource_by_namespace::<()>("hello"),
+            Err(PropertyError::ResourceNotFound("hello", "()"))
+        ));
+        assert_eq!(vec![""], env.resource_namespaces::<()>());
+    }
+
+    struct FailedTask;
+
+    impl Resource for FailedTask {
+        type Config = ();
+        type Customizer = ();
+
+        fn create(
+            _: Self::Config,
+            _: &FactoryContext<'_>,
+            _: impl FnOnce(&mut Self::Customizer, &Self::Config) -> Void,
+        ) -> Res<Self> {
+            Ok(FailedTask)
+        }
+
+        fn register_dependent_resources(builder: &mut FactoryBuilder<'_>) -> Void {
+            builder.submit(|_: Arc<FailedTask>| Err(PropertyError::parse_fail("task failed")))
+        }
+    }
+
+    struct PanickedTask;
+
+    impl Resource for PanickedTask {
+        type Config = ();
+        type Customizer = ();
+
+        fn create(
+            _: Self::Config,
+            _: &FactoryContext<'_>,
+            _: impl FnOnce(&mut Self::Customizer, &Self::Config) -> Void,
+        ) -> Res<Self> {
+            Ok(PanickedTask)
+        }
+
+        fn register_dependent_resources(builder: &mut FactoryBuilder<'_>) -> Void {
+            builder.submit(|_: Arc<PanickedTask>| panic!("task panicked"))
+        }
+    }
+
+    struct NamedTask;
+
+    impl Resource for NamedTask {
+        type Config = ();
+        type Customizer = ();
+
+        fn create(
+            _: Self::Config,
+            _: &FactoryContext<'_>,
+            _: impl FnOnce(&mut Self::Customizer, &Self::Config) -> Void,
+        ) -> Res<Self> {
+            Ok(NamedTask)
+        }
+
+        fn register_dependent_resources(builder: &mut FactoryBuilder<'_>) -> Void {
+            builder.submit_named("metric-scrape", |_: Arc<NamedTask>| {
+                assert_eq!(Some("metric-scrape"), std::thread::current().name());
+                Err(PropertyError::parse_fail("scrape failed"))
+            })?;
+            builder.submit_named("heartbeat", |_: Arc<NamedTask>| Ok(()))
+        }
+    }
+
+    #[test]
+    fn run_test() {
+        let mut env = Salak::builder()
+            .register_default_resource::<FailedTask>()
+            .unwrap()
+            .build()
+            .unwrap();
+        let err = format!("{:?}", env.run().unwrap_err());
+        assert!(err.contains("task failed"));
+        assert!(env.run().is_ok());
+
+        let mut env = Salak::builder()
+            .register_default_resource::<FailedTask>()
+            .unwrap()
+            .register_default_resource::<PanickedTask>()
+            .unwrap()
+            .build()
+            .unwrap();
+        let err = env.run().unwrap_err();
+        match err
+            .parse_cause()
+            .and_then(|e| e.downcast_ref::<TaskError>())
+        {
+            Some(TaskError::Multiple(errs)) => {
+                assert!(matches!(
+                    &errs[0],
+                    TaskError::Failed(None, PropertyError::ParseFail(None, _))
+                ));
+                assert!(
+                    matches!(&errs[1], TaskError::Panicked(None, msg) if msg == "task panicked")
+                );
+            }
+            _ => panic!("unexpected error {:?}", err),
+        }
+        let err = err.to_string();
+        assert!(err.contains("2 tasks failed"));
+        assert!(err.contains("task failed"));
+        assert!(err.contains("task panicked"));
+
+        let mut env = Salak::builder()
+            .register_default_resource::<NamedTask>()
+            .unwrap()
+            .build()
+            .unwrap();
+        let err = env.run().unwrap_err();
+        assert!(matches!(
+            err.parse_cause().and_then(|e| e.downcast_ref::<TaskError>()),
+            Some(TaskError::Failed(Some(name), _)) if name == "metric-scrape"
+        ));
+        let err = err.to_string();
+        assert!(err.contains("task 'metric-scrape' failed"));
+        assert!(err.contains("scrape failed"));
+        assert!(!err.contains("heartbeat"));
+    }
+
+    static SLOW_DONE: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+    struct SlowTask;
+
+    impl Resource for SlowTask {
+        type Config = ();
+        type Customizer = ();
+
+        fn create(
+            _: Self::Config,
+            _: &FactoryContext<'_>,
+            _: impl FnOnce(&mut Self::Customizer, &Self::Config) -> Void,
+        ) -> Res<Self> {
+            Ok(SlowTask)
+        }
+
+        fn register_dependent_resources(builder: &mut FactoryBuilder<'_>) -> Void {
+            builder.submit(|_: Arc<SlowTask>| {
+                std::thread::sleep(std::time::Duration::from_millis(50));
+                SLOW_DONE.store(true, std::sync::atomic::Ordering::SeqCst);
+                Ok(())
+            })
+        }
+    }
+
+    struct BrokenTask;
+
+    impl Resource for BrokenTask {
+        type Config = ();
+        type Customizer = ();
+
+        fn create(
+            _: Self::Config,
+            _: &FactoryContext<'_>,
+            _: impl FnOnce(&mut Self::Customizer, &Self::Config) -> Void,
+        ) -> Res<Self> {
+            Err(PropertyError::parse_fail("broken"))
+        }
+
+        fn register_dependent_resources(builder: &mut FactoryBuilder<'_>) -> Void {
+            builder.submit(|_: Arc<BrokenTask>| Ok(()))
+        }
+    }
+
+    #[test]
+    fn run_start_fail_test() {
+        // Task of broken resource fails to start, started tasks are still joined.
+        let mut env = Salak::builder()
+            .defer_init(true)
+            .register_default_resource::<SlowTask>()
+            .unwrap()
+            .register_default_resource::<BrokenTask>()
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(
+            1,
+            env.init_all_resources()
+                .values()
+                .filter(|v| v.is_err())
+                .count()
+        );
+        match env.run() {
+            Err(PropertyError::ResourceNotFound(_, name)) => assert!(name.ends_with("BrokenTask")),
+            v => panic!("unexpected result {:?}", v),
+        }
+        assert!(SLOW_DONE.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    struct Greeting(String);
+
+    impl Resource for Greeting {
+        type Config = ();
+        type Customizer = ();
+
+        fn create(
+            _: Self::Config,
+            factory: &FactoryContext<'_>,
+            _: impl FnOnce(&mut Self::Customizer, &Self::Config) -> Void,
+        ) -> Res<Self> {
+            let name = factory.env().require::<String>("app.user")?;
+            Ok(Greeting(format!("hello {}", name)))
+        }
+    }
+
+    #[test]
+    fn factory_env_test() {
+        let env = Salak::builder()
+            .set("app.user", "salak")
+            .register_default_resource::<Greeting>()
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!("hello salak", env.get_resource::<Greeting>().unwrap().0);
+        assert!(Salak::builder()
+            .register_default_resource::<Greeting>()
+            .unwrap()
+            .build()
+            .is_err());
+    }
+
+    #[derive(FromEnvironment)]
+    #[salak(prefix = "redis.instances")]
+    struct RedisConfig {
+        host: String,
+    }
+
+    struct Redis(String);
+
+    impl Resource for Redis {
+        type Config = RedisConfig;
+        type Customizer = ();
+
+        fn create(
+            config: Self::Config,
+            _: &FactoryContext<'_>,
+            _: impl FnOnce(&mut Self::Customizer, &Self::Config) -> Void,
+        ) -> Res<Self> {
+            Ok(Redis(config.host))
+        }
+    }
+
+    #[test]
+    fn register_resources_from_test() {
+        let env = Salak::builder()
+            .set("redis.instances.a.host", "10.0.0.1")
+            .set("redis.instances.b.host", "10.0.0.2")
+            .register_resources_from::<Redis>("redis.instances")
+            .build()
+            .unwrap();
+        assert_eq!(
+            "10.0.0.1",
+            env.get_resource_by_namespace::<Redis>("a").unwrap().0
+        );
+        assert_eq!(
+            "10.0.0.2",
+            env.get_resource_by_namespace::<Redis>("b").unwrap().0
+        );
+        assert!(env.get_resource::<Redis>().is_err());
+
+        // Discovered namespaces are interned, building again does not leak them again.
+        let build = || {
+            Salak::builder()
+                .set("redis.instances.a.host", "10.0.0.1")
+                .register_resources_from::<Redis>("redis.instances")
+                .build()
+                .unwrap()
+                .resource_namespaces::<Redis>()
+        };
+        let (first, second) = (build(), build());
+        assert_eq!(vec!["a"], first);
+        assert!(std::ptr::eq(first[0], second[0]));
+    }
+
+    struct Cluster;
+
+    impl Resource for Cluster {
+        type Config = ();
+        type Customizer = ();
+
+        fn create(
+            _: Self::Config,
+            factory: &FactoryContext<'_>,
+            _: impl FnOnce(&mut Self::Customizer, &Self::Config) -> Void,
+        ) -> Res<Self> {
+            assert_eq!(2, factory.get_all_resources::<Redis>()?.len());
+            Ok(Cluster)
+        }
+
+        fn register_dependent_resources(builder: &mut FactoryBuilder<'_>) -> Void {
+            builder.register_resources_from::<Redis>("redis.instances")
+        }
+    }
+
+    #[test]
+    fn register_dependent_resources_from_test() {
+        let env = Salak::builder()
+            .set("redis.instances.a.host", "10.0.0.1")
+            .set("redis.instances.b.host", "10.0.0.2")
+            .register_default_resource::<Cluster>()
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(env.get_resource::<Cluster>().is_ok());
+        assert_eq!(
+            "10.0.0.2",
+            env.get_resource_by_namespace::<Redis>("b").unwrap().0
+        );
+        assert!(env.get_resource::<Redis>().is_err());
+    }
+
+    #[derive(FromEnvironment)]
+    #[salak(prefix = "client")]
+    struct ClientConfig {
+        #[salak(default = 1)]
+        timeout: u64,
+    }
+
+    static TIMEOUT: AtomicU64 = AtomicU64::new(1);
+    static CREATED: AtomicUsize = AtomicUsize::new(0);
+
+    macro_rules! impl_client_resource {
+        ($($x:ident => $r:expr),+) => {$(
+            struct $x(u64);
+
+            impl Resource for $x {
+                type Config = ClientConfig;
+                type Customizer = ();
+
+                fn create(
+                    config: Self::Config,
+                    _: &FactoryContext<'_>,
+                    _: impl FnOnce(&mut Self::Customizer, &Self::Config) -> Void,
+                ) -> Res<Self> {
+                    CREATED.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    Ok($x(config.timeout))
+                }
+
+                fn reloadable() -> bool {
+                    $r
+                }
+            }
+        )+};
+    }
+
+    impl_client_resource!(Client => true, FixedClient => false);
+
+    struct Timeout(u64);
+
+    impl PropertySource for Timeout {
+        fn name(&self) -> &str {
+            "timeout"
+        }
+
+        fn get_property(&self, key: &Key<'_>) -> Option<Property<'_>> {
+            match key.as_str() {
+                "client.timeout" => Some(Property::I(self.0.into())),
+                _ => None,
+            }
+        }
+
+        fn get_sub_keys<'a>(&'a self, key: &Key<'_>, sub_keys: &mut SubKeys<'a>) {
+            if key.as_str() == "client" {
+                sub_keys.insert("timeout");
+            }
+        }
+
+        fn is_empty(&self) -> bool {
+            false
+        }
+
+        fn reload_source(&self) -> Res<Option<Box<dyn PropertySource>>> {
+            Ok(Some(Box::new(Timeout(
+                TIMEOUT.load(std::sync::atomic::Ordering::SeqCst),
+            ))))
+        }
+    }
+
+    #[test]
+    fn config_prefix_test() {
+        let env = Salak::builder()
+            .set("redis.instances.host", "10.0.0.1")
+            .set("cache.redis.host", "10.0.0.2")
+            .set("cache.redis.a.host", "10.0.0.3")
+            .register_default_resource::<Redis>()
+            .unwrap()
+            .register_resource(ResourceBuilder::<Redis>::new("a").config_prefix("cache.redis"))
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!("10.0.0.1", env.get_resource::<Redis>().unwrap().0);
+        assert_eq!(
+            "10.0.0.3",
+            env.get_resource_by_namespace::<Redis>("a").unwrap().0
+        );
+        assert_eq!(
+            "10.0.0.2",
+            env.init_resource_with_builder(
+                ResourceBuilder::<Redis>::default().config_prefix("cache.redis")
+            )
+            .unwrap()
+            .0
+        );
+    }
+
+    #[test]
+    fn get_or_init_resource_test() {
+        let env = Salak::builder()
+            .set("redis.instances.host", "10.0.0.1")
+            .build()
+            .unwrap();
+        assert!(env.get_resource::<Redis>().is_err());
+        let redis = env.get_or_init_resource::<Redis>().unwrap();
+        assert_eq!("10.0.0.1", redis.0);
+        assert!(Arc::ptr_eq(
+            &redis,
+            &env.get_or_init_resource::<Redis>().unwrap()
+        ));
+        assert!(env.get_resource::<Redis>().is_err());
+        assert!(env
+            .health()
+            .contains_key(&(std::any::type_name::<Redis>(), "")));
+
+        let env = Salak::builder()
+            .set("redis.instances.host", "10.0.0.1")
+            .register_default_resource::<Redis>()
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(Arc::ptr_eq(
+            &env.get_resource::<Redis>().unwrap(),
+            &env.get_or_init_resource::<Redis>().unwrap()
+        ));
+    }
+
+    #[test]
+    fn reload_resource_test() {
+        let mut env = Salak::builder()
+            .register_default_resource::<Client>()
+            .unwrap()
+            .register_default_resource::<FixedClient>()
+            .unwrap()
+            .build()
+            .unwrap();
+        env.register(Timeout(1));
+        let created = || CREATED.load(std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(2, created());
+        assert_eq!(1, env.get_resource::<Client>().unwrap().0);
+
+        TIMEOUT.store(5, std::sync::atomic::Ordering::SeqCst);
+        env.reload().unwrap();
+        assert_eq!(3, created());
+        assert_eq!(5, env.get_resource::<Client>().unwrap().0);
+        assert_eq!(1, env.get_resource::<FixedClient>().unwrap().0);
+
+        env.reload().unwrap();
+        assert_eq!(3, created());
+        assert_eq!(5, env.get_resource::<Client>().unwrap().0);
+    }
+
     use std::sync::Arc;
     generate_service!(X { a: Option<()>, b: ()});
     generate_service!(Y { a: Option<()>});
@@ -733,4 +2114,94 @@ mod tests {
         b:(),
         a: Option<()>
     });
+
+    generate_service!(Configured { env: EnvHandle });
+
+    struct Named(&'static str);
+
+    impl Resource for Named {
+        type Config = ();
+        type Customizer = ();
+
+        fn create(
+            _: Self::Config,
+            factory: &FactoryContext<'_>,
+            _: impl FnOnce(&mut Self::Customizer, &Self::Config) -> Void,
+        ) -> Res<Self> {
+            Ok(Named(factory.current_namespace()))
+        }
+    }
+
+    #[derive(Service)]
+    #[salak(default_namespace = "hello")]
+    struct DefaultNamespaced {
+        a: Arc<Named>,
+        b: Option<Arc<Named>>,
+        #[salak(namespace = "")]
+        c: Arc<Named>,
+    }
+
+    #[test]
+    fn default_namespace_test() {
+        let env = Salak::builder()
+            .register_default_resource::<Named>()
+            .unwrap()
+            .register_resource::<Named>(ResourceBuilder::new("hello"))
+            .unwrap()
+            .build()
+            .unwrap();
+        let service = env.get_service::<DefaultNamespaced>().unwrap();
+        assert_eq!("hello", service.as_a().0);
+        assert_eq!(Some("hello"), service.as_b().map(|v| v.0));
+        assert_eq!("", service.as_c().0);
+    }
+
+    #[test]
+    fn env_handle_test() {
+        let mut env = Salak::builder()
+            .set("app.name", "salak")
+            .set("app.greeting", "hello ${app.name}")
+            .set("app.raw", "\\${app.name\\}")
+            .set("app.port", "8080")
+            .register_default_resource::<EnvHandle>()
+            .unwrap()
+            .build()
+            .unwrap();
+        let service = env.get_service::<Configured>().unwrap();
+        assert_eq!(
+            "hello salak",
+            service.env.require::<String>("app.greeting").unwrap()
+        );
+        assert_eq!(
+            "${app.name}",
+            service.env.require::<String>("app.raw").unwrap()
+        );
+        assert_eq!(8080, service.env.require::<u16>("app.port").unwrap());
+        assert_eq!(
+            None,
+            service.env.require::<Option<u16>>("app.none").unwrap()
+        );
+        assert!(!service.env.reload().unwrap());
+
+        env.set_runtime("app.port", "9090");
+        assert_eq!(9090, service.env.require::<u16>("app.port").unwrap());
+
+        let source = crate::source::HashMapSource::new("shared").into_shared();
+        env.register(source.clone());
+        source.set("app.extra", "7");
+        assert_eq!(
+            None,
+            service.env.require::<Option<u8>>("app.extra").unwrap()
+        );
+        env.reload().unwrap();
+        let extra = service
+            .env
+            .require::<wrapper::IORef<u8>>("app.extra")
+            .unwrap();
+        assert_eq!(7, extra.get_val().unwrap());
+        source.set("app.extra", "8");
+        env.reload().unwrap();
+        assert_eq!(8, service.env.require::<u8>("app.extra").unwrap());
+        assert_eq!(8, extra.get_val().unwrap());
+    }
 }
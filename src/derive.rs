@@ -17,8 +17,21 @@ pub struct SalakDescContext<'a> {
     pub(crate) current: KeyDesc,
 }
 
+impl SalakDescContext<'_> {
+    /// Describe current key as a single property, same as [`IsProperty`] types.
+    /// Used by `#[derive(DescFromEnvironment)]`.
+    #[inline]
+    pub fn leaf_desc(&mut self) {
+        self.current.ignore = false;
+        self.current.set_required(true);
+    }
+}
+
 #[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
 /// Generate description for this object.
+///
+/// Collections describe their elements recursively, such as `servers[*].host` for
+/// `Vec<Server>` and `servers.<key>.host` for `HashMap<String, Server>`.
 pub trait DescFromEnvironment: FromEnvironment {
     /// Generate key description from [`SalakDescContext`].
     /// * `env` - Describable context.
@@ -31,6 +44,13 @@ pub trait DescFromEnvironment: FromEnvironment {
 pub trait PrefixedFromEnvironment: DescFromEnvironment {
     /// Set configuration prefix.
     fn prefix() -> &'static str;
+
+    /// Resolve configuration prefix by `env`, placeholders such as `${app.name}.server`
+    /// are expanded.
+    #[inline]
+    fn prefix_resolved(env: &Salak) -> Res<String> {
+        env.resolve_placeholder(Self::prefix())
+    }
 }
 
 #[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
@@ -40,10 +60,10 @@ impl<P: PrefixedFromEnvironment> PrefixedFromEnvironment for Option<P> {
         P::prefix()
     }
 }
-/// Key Description
+/// Key Description, see [`Salak::describe_namespaced()`].
 #[derive(Debug)]
 #[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
-pub(crate) struct KeyDesc {
+pub struct KeyDesc {
     key: String,
     tp: &'static str,
     pub(crate) required: Option<bool>,
@@ -136,6 +156,31 @@ impl KeyDesc {
             self.required = Some(required);
         }
     }
+
+    /// Full key, such as `postgresql.replica.host`.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Type name of value.
+    pub fn type_name(&self) -> &'static str {
+        self.tp
+    }
+
+    /// Whether the key is required.
+    pub fn required(&self) -> bool {
+        self.required.unwrap_or(true)
+    }
+
+    /// Default value.
+    pub fn default_value(&self) -> Option<&str> {
+        self.def.as_deref()
+    }
+
+    /// Description of the key.
+    pub fn description(&self) -> Option<&str> {
+        self.desc.as_deref()
+    }
 }
 
 #[cfg(test)]
@@ -198,6 +243,463 @@ mod tests {
         println!("{:?}", env.require::<Value>("hello"))
     }
 
+    #[derive(FromEnvironment, Debug, PartialEq)]
+    #[salak(prefix = "salak.level")]
+    enum Level {
+        #[salak(name = "WARN")]
+        Warning,
+        Info,
+    }
+
+    #[test]
+    fn enum_attr_test() {
+        let env = Salak::builder()
+            .set("salak.level", "warn")
+            .set("info", "info")
+            .set("warning", "warning")
+            .build()
+            .unwrap();
+        assert_eq!(Level::Warning, env.get::<Level>().unwrap());
+        assert_eq!(Level::Info, env.require::<Level>("info").unwrap());
+        assert!(env.require::<Level>("warning").is_err());
+        assert_eq!(
+            "Err(ParseFail(Some(\"warning\"), SalakParseError(\"invalid enum value 'warning', expected one of: warn, info\")))",
+            format!("{:?}", env.require::<Level>("warning"))
+        );
+    }
+
+    #[derive(FromEnvironment, Debug)]
+    struct LenConfig {
+        #[salak(min_len = 8)]
+        password: String,
+        #[salak(max_len = 2, default = "")]
+        hosts: Vec<String>,
+        #[salak(min_len = 2, max_len = 3)]
+        name: Option<String>,
+    }
+
+    #[test]
+    fn len_test() {
+        let env = Salak::builder()
+            .set("a.password", "12345678")
+            .set("a.hosts[0]", "x")
+            .set("a.hosts[1]", "y")
+            .set("a.name", "abc")
+            .set("b.password", "1234567")
+            .set("c.password", "12345678")
+            .set("c.hosts[0]", "x")
+            .set("c.hosts[1]", "y")
+            .set("c.hosts[2]", "z")
+            .set("d.password", "12345678")
+            .set("d.name", "a")
+            .set("e.password", "12345678")
+            .set("e.name", "abcd")
+            .build()
+            .unwrap();
+        let config = env.require::<LenConfig>("a").unwrap();
+        assert_eq!("12345678", config.password);
+        assert_eq!(2, config.hosts.len());
+        assert_eq!(Some("abc"), config.name.as_deref());
+        assert_eq!(
+            "Err(ParseFail(Some(\"b.password\"), SalakParseError(\"length 7 is less than min_len 8\")))",
+            format!("{:?}", env.require::<LenConfig>("b").map(|_| ()))
+        );
+        assert_eq!(
+            "Err(ParseFail(Some(\"c.hosts\"), SalakParseError(\"length 3 is greater than max_len 2\")))",
+            format!("{:?}", env.require::<LenConfig>("c").map(|_| ()))
+        );
+        assert!(env.require::<LenConfig>("d").is_err());
+        assert!(env.require::<LenConfig>("e").is_err());
+        assert!(env
+            .require::<LenConfig>("e")
+            .err()
+            .map(|e| format!("{:?}", e).contains("e.name"))
+            .unwrap());
+    }
+
+    #[derive(Prefixed, Debug)]
+    #[salak(prefix = "manual")]
+    struct Manual {
+        hosts: String,
+    }
+
+    impl FromEnvironment for Manual {
+        fn from_env(_: Option<Property<'_>>, env: &mut SalakContext<'_>) -> Res<Self> {
+            let hosts: Vec<String> = env.require_def("hosts", None)?;
+            Ok(Manual {
+                hosts: hosts.join(","),
+            })
+        }
+    }
+
+    #[test]
+    fn prefixed_test() {
+        let env = Salak::builder()
+            .set("manual.hosts[0]", "a")
+            .set("manual.hosts[1]", "b")
+            .build()
+            .unwrap();
+        assert_eq!("manual", Manual::prefix());
+        assert_eq!("a,b", env.get::<Manual>().unwrap().hosts);
+        assert!(Salak::builder()
+            .configure_description::<Manual>()
+            .build()
+            .is_ok());
+    }
+
+    #[derive(FromEnvironment, Debug)]
+    #[salak(prefix = "trim")]
+    struct TrimConfig {
+        #[salak(trim)]
+        name: String,
+        #[salak(trim)]
+        nick: Option<String>,
+        #[salak(trim, default = 8080)]
+        port: u16,
+        #[salak(trim, alias = "old_code", min_len = 2)]
+        code: Option<String>,
+    }
+
+    #[test]
+    fn trim_test() {
+        let env = Salak::builder()
+            .set("trim.name", "  salak \t")
+            .set("trim.nick", "   ")
+            .set("trim.port", "   ")
+            .set("trim.old_code", " ab ")
+            .build()
+            .unwrap();
+        let config = env.get::<TrimConfig>().unwrap();
+        assert_eq!("salak", config.name);
+        assert_eq!(None, config.nick);
+        assert_eq!(8080, config.port);
+        assert_eq!(Some("ab"), config.code.as_deref());
+
+        let env = Salak::builder()
+            .set("trim.name", " salak")
+            .set("trim.nick", " s ")
+            .set("trim.port", " 80 ")
+            .set("trim.code", " a ")
+            .build()
+            .unwrap();
+        assert!(env.get::<TrimConfig>().is_err());
+        let env = Salak::builder()
+            .set("trim.name", " salak")
+            .set("trim.nick", " s ")
+            .set("trim.port", " 80 ")
+            .build()
+            .unwrap();
+        let config = env.get::<TrimConfig>().unwrap();
+        assert_eq!(Some("s"), config.nick.as_deref());
+        assert_eq!(80, config.port);
+
+        let env = Salak::builder().set("trim.name", " ").build().unwrap();
+        assert!(matches!(
+            env.get::<TrimConfig>(),
+            Err(PropertyError::NotFound(_))
+        ));
+    }
+
+    #[derive(Debug, DescFromEnvironment)]
+    struct Verbosity(u8);
+
+    impl FromEnvironment for Verbosity {
+        fn from_env(val: Option<Property<'_>>, env: &mut SalakContext<'_>) -> Res<Self> {
+            match val {
+                Some(Property::S("low")) => Ok(Verbosity(1)),
+                Some(Property::S("high")) => Ok(Verbosity(9)),
+                Some(_) => Err(PropertyError::parse_fail("unknown level")),
+                None => Err(PropertyError::NotFound(env.current_key().to_string())),
+            }
+        }
+    }
+
+    #[derive(FromEnvironment, Debug)]
+    #[salak(prefix = "salak")]
+    struct VerbosityConfig {
+        #[salak(desc = "log level")]
+        level: Verbosity,
+        levels: Vec<Verbosity>,
+    }
+
+    #[derive(FromEnvironment, Debug)]
+    #[salak(prefix = "required")]
+    struct RequiredConfig {
+        #[salak(required)]
+        name: Option<String>,
+        #[salak(required, alias = "old_port")]
+        port: Option<u16>,
+        nick: Option<String>,
+    }
+
+    #[test]
+    fn required_test() {
+        let env = Salak::builder()
+            .set("required.name", "salak")
+            .set("required.old_port", "80")
+            .build()
+            .unwrap();
+        let config = env.get::<RequiredConfig>().unwrap();
+        assert_eq!(Some("salak"), config.name.as_deref());
+        assert_eq!(Some(80), config.port);
+        assert_eq!(None, config.nick);
+
+        let env = Salak::builder().set("required.port", "80").build().unwrap();
+        match env.get::<RequiredConfig>() {
+            Err(PropertyError::NotFound(key)) => assert_eq!("required.name", key),
+            v => panic!("unexpected {:?}", v),
+        }
+
+        let desc = env.get_desc::<RequiredConfig>("");
+        assert_eq!(Some(true), desc[0].required);
+        assert_eq!(Some(true), desc[1].required);
+        assert_eq!(Some(false), desc[2].required);
+    }
+
+    #[test]
+    fn desc_derive_test() {
+        let env = Salak::builder()
+            .set("salak.level", "high")
+            .set("salak.levels[0]", "low")
+            .build()
+            .unwrap();
+        let config = env.get::<VerbosityConfig>().unwrap();
+        assert_eq!(9, config.level.0);
+        assert_eq!(1, config.levels[0].0);
+
+        let desc = env.get_desc::<VerbosityConfig>("");
+        assert_eq!(2, desc.len());
+        assert_eq!(Some(true), desc[0].required);
+        assert_eq!(Some("log level"), desc[0].desc.as_deref());
+        assert_eq!(Some(false), desc[1].required);
+    }
+
+    #[allow(dead_code)]
+    #[derive(FromEnvironment, Debug)]
+    #[salak(prefix = "postgresql")]
+    struct PostgresConfig {
+        #[salak(desc = "database host")]
+        host: String,
+        #[salak(default = 5432)]
+        port: u16,
+    }
+
+    #[test]
+    fn describe_namespaced_test() {
+        let env = Salak::new().unwrap();
+        for namespace in &["primary", "replica"] {
+            let desc = env.describe_namespaced::<PostgresConfig>(namespace);
+            assert_eq!(
+                vec![
+                    format!("postgresql.{}.host", namespace),
+                    format!("postgresql.{}.port", namespace)
+                ],
+                desc.iter().map(|d| d.key().to_string()).collect::<Vec<_>>()
+            );
+            assert!(desc[0].required());
+            assert_eq!(Some("database host"), desc[0].description());
+            assert!(!desc[1].required());
+            assert_eq!(Some("5432"), desc[1].default_value());
+        }
+        assert_eq!(
+            "postgresql.host",
+            env.describe_namespaced::<PostgresConfig>("")[0].key()
+        );
+    }
+
+    #[derive(FromEnvironment, Debug)]
+    #[salak(prefix = "${app.name}.server")]
+    struct AppServer {
+        port: u16,
+    }
+
+    #[test]
+    fn prefix_resolved_test() {
+        let env = Salak::builder()
+            .set("app.name", "hello")
+            .set("hello.server.port", "8080")
+            .build()
+            .unwrap();
+        assert_eq!("hello.server", AppServer::prefix_resolved(&env).unwrap());
+        assert_eq!(8080, env.get::<AppServer>().unwrap().port);
+        assert_eq!(8080, env.get::<Option<AppServer>>().unwrap().unwrap().port);
+        let env = Salak::builder()
+            .set("hello.server.port", "8080")
+            .build()
+            .unwrap();
+        assert!(matches!(
+            env.get::<AppServer>(),
+            Err(PropertyError::ResolveNotFound(_))
+        ));
+    }
+
+    #[derive(FromEnvironment, Debug, PartialEq)]
+    enum Mode {
+        Fast = 2,
+        Slow,
+    }
+
+    #[derive(FromEnvironment, Debug, PartialEq)]
+    #[salak(numeric_only)]
+    enum Code {
+        Ok = 200,
+        NotFound = 404,
+    }
+
+    #[test]
+    fn enum_numeric_test() {
+        let env = Salak::builder()
+            .set("a", "fast")
+            .set("b", "2")
+            .set("c", "3")
+            .set("d", "4")
+            .set("e", "404")
+            .set("f", "ok")
+            .build()
+            .unwrap();
+        assert_eq!(Mode::Fast, env.require::<Mode>("a").unwrap());
+        assert_eq!(Mode::Fast, env.require::<Mode>("b").unwrap());
+        assert_eq!(Mode::Slow, env.require::<Mode>("c").unwrap());
+        assert!(env.require::<Mode>("d").is_err());
+        assert_eq!(Code::NotFound, env.require::<Code>("e").unwrap());
+        assert!(format!("{:?}", env.require::<Code>("f"))
+            .contains("invalid enum value 'ok', expected one of: 200, 404"));
+        assert_eq!(
+            Ok(Code::Ok),
+            Code::from_property(Property::I(200)).map_err(|_| ())
+        );
+    }
+
+    #[cfg(feature = "log")]
+    #[derive(FromEnvironment, Debug)]
+    #[salak(prefix = "salak")]
+    struct DeprecatedConfig {
+        #[salak(deprecated = "use salak.new_key instead")]
+        old_key: Option<u8>,
+        new_key: Option<u8>,
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn deprecated_test() {
+        use crate::test_log::{any_log, init};
+        init();
+
+        let env = Salak::builder().set("salak.new_key", "1").build().unwrap();
+        assert_eq!(Some(1), env.get::<DeprecatedConfig>().unwrap().new_key);
+        assert!(!any_log(log::Level::Warn, |v| v.contains("salak.old_key")));
+
+        let env = Salak::builder().set("salak.old_key", "1").build().unwrap();
+        let config = env.get::<DeprecatedConfig>().unwrap();
+        assert_eq!(Some(1), config.old_key);
+        assert!(any_log(log::Level::Warn, |v| v.contains("salak.old_key")
+            && v.contains("use salak.new_key instead")));
+
+        let desc = env.get_desc::<DeprecatedConfig>("");
+        assert_eq!(
+            Some("[Deprecated: use salak.new_key instead]"),
+            desc[0].desc.as_deref()
+        );
+    }
+
+    #[derive(FromEnvironment, Debug)]
+    #[salak(prefix = "salak")]
+    struct AliasConfig {
+        #[salak(alias = "old_name", alias = "legacy.name", default = 1)]
+        name: u8,
+    }
+
+    #[test]
+    fn alias_test() {
+        let env = Salak::builder().build().unwrap();
+        assert_eq!(1, env.get::<AliasConfig>().unwrap().name);
+        let env = Salak::builder().set("salak.name", "2").build().unwrap();
+        assert_eq!(2, env.get::<AliasConfig>().unwrap().name);
+        let env = Salak::builder()
+            .set("salak.legacy.name", "3")
+            .build()
+            .unwrap();
+        assert_eq!(3, env.get::<AliasConfig>().unwrap().name);
+        let env = Salak::builder()
+            .set("salak.name", "2")
+            .set("salak.legacy.name", "3")
+            .set("salak.old_name", "4")
+            .build()
+            .unwrap();
+        assert_eq!(4, env.get::<AliasConfig>().unwrap().name);
+    }
+
+    #[derive(FromEnvironment, Debug)]
+    #[salak(prefix = "salak.wrapper")]
+    struct Wrapper<T> {
+        inner: T,
+        #[salak(default = "1")]
+        outer: Option<T>,
+    }
+
+    #[test]
+    fn generic_test() {
+        let env = Salak::builder()
+            .set("salak.wrapper.inner", "3")
+            .build()
+            .unwrap();
+        let wrapper = env.get::<Wrapper<u8>>().unwrap();
+        assert_eq!(3, wrapper.inner);
+        assert_eq!(Some(1), wrapper.outer);
+        assert!(env.get::<Wrapper<bool>>().is_err());
+        assert_eq!(2, env.get_desc::<Wrapper<u8>>("").len());
+    }
+
+    #[derive(FromEnvironment, Debug)]
+    struct SubConfig {
+        #[salak(default = "1")]
+        num: u8,
+    }
+
+    #[derive(FromEnvironment, Debug)]
+    #[salak(prefix = "salak.pointer")]
+    struct PointerConfig {
+        arc: std::sync::Arc<SubConfig>,
+        rc: std::rc::Rc<SubConfig>,
+        opt: Option<std::sync::Arc<SubConfig>>,
+    }
+
+    #[test]
+    fn pointer_test() {
+        let env = Salak::builder()
+            .set("salak.pointer.rc.num", "2")
+            .build()
+            .unwrap();
+        let config = env.get::<PointerConfig>().unwrap();
+        assert_eq!(1, config.arc.num);
+        assert_eq!(2, config.rc.num);
+        assert_eq!(1, config.opt.unwrap().num);
+        assert_eq!(3, env.get_desc::<PointerConfig>("").len());
+    }
+
+    #[derive(FromEnvironment, Debug)]
+    #[salak(prefix = "salak.def")]
+    struct DefaultConfig {
+        #[salak(default = 8080)]
+        port: u16,
+        #[salak(default = "${salak.def.other:9090}")]
+        admin_port: Option<u16>,
+        #[salak(default = "yes")]
+        enabled: bool,
+        #[salak(default = "1.5")]
+        ratio: f32,
+    }
+
+    #[test]
+    fn default_check_test() {
+        let env = Salak::builder().build().unwrap();
+        let config = env.get::<DefaultConfig>().unwrap();
+        assert_eq!(8080, config.port);
+        assert_eq!(Some(9090), config.admin_port);
+        assert!(config.enabled);
+        assert_eq!(1.5, config.ratio);
+    }
+
     #[test]
     fn derive_fail_test() {
         let t = trybuild::TestCases::new();
@@ -1,14 +1,24 @@
 use parking_lot::Mutex;
 #[cfg(feature = "app")]
-use std::any::Any;
-use std::collections::HashMap;
+use parking_lot::RwLock;
+use std::{
+    any::{Any, TypeId},
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::{
+        mpsc::{channel, Receiver},
+        Arc,
+    },
+};
 
 #[cfg(feature = "args")]
 use crate::AppInfo;
 
 use crate::{
-    raw_ioref::IORefT, source_raw::PropertyRegistryInternal, Environment, FromEnvironment,
-    PropertySource, Res,
+    raw_ioref::{IORefT, KeyWatcher},
+    source_map::RuntimeSource,
+    source_raw::PropertyRegistryInternal,
+    Environment, FromEnvironment, IsProperty, Key, Property, PropertyError, PropertySource, Res,
+    SubKeys,
 };
 #[cfg(feature = "app")]
 use crate::{Resource, ResourceBuilder, ResourceRegistry};
@@ -16,16 +26,20 @@ use crate::{Resource, ResourceBuilder, ResourceRegistry};
 #[allow(unused_imports)]
 use crate::source_raw::FileConfig;
 #[cfg(feature = "derive")]
-use crate::{DescFromEnvironment, Key, KeyDesc, PrefixedFromEnvironment, SalakDescContext};
+use crate::{DescFromEnvironment, KeyDesc, PrefixedFromEnvironment, SalakDescContext};
 
 /// A builder which can configure for how to build a salak env.
 #[allow(missing_debug_implementations)]
 pub struct SalakBuilder {
-    args: HashMap<String, String>,
-    #[cfg(any(feature = "toml", feature = "yaml"))]
+    args: HashMap<String, Property<'static>>,
+    #[cfg(any(feature = "toml", feature = "yaml", feature = "properties"))]
     disable_file: bool,
+    #[cfg(any(feature = "toml", feature = "yaml", feature = "properties"))]
+    deny_empty_file: bool,
     #[cfg(feature = "rand")]
     disable_random: bool,
+    disable_env: bool,
+    env_map: Option<HashMap<String, String>>,
     registry: PropertyRegistryInternal<'static>,
     #[cfg(any(feature = "args", feature = "derive"))]
     pub(crate) app_desc: Vec<Box<dyn Fn(&mut Salak) -> Vec<KeyDesc>>>,
@@ -34,6 +48,10 @@ pub struct SalakBuilder {
     iorefs: Mutex<Vec<Box<dyn IORefT + Send>>>,
     #[cfg(feature = "app")]
     resource: ResourceRegistry,
+    #[cfg(feature = "app")]
+    parallel_init: bool,
+    #[cfg(feature = "app")]
+    defer_init: bool,
 }
 
 #[allow(dead_code)]
@@ -43,24 +61,73 @@ impl SalakBuilder {
     /// Set custom arguments properties.
     #[inline]
     pub fn set_args(mut self, args: HashMap<String, String>) -> Self {
-        self.args.extend(args);
+        self.args
+            .extend(args.into_iter().map(|(k, v)| (k, Property::O(v))));
+        self
+    }
+
+    /// Set a group of custom properties, such as a slice of tuples or entries of a map.
+    pub fn set_all<K: Into<String>, V: Into<String>, I: IntoIterator<Item = (K, V)>>(
+        mut self,
+        iter: I,
+    ) -> Self {
+        self.args.extend(
+            iter.into_iter()
+                .map(|(k, v)| (k.into(), Property::O(v.into()))),
+        );
         self
     }
 
     /// Set custom property.
     pub fn set<K: Into<String>, V: Into<String>>(mut self, k: K, v: V) -> Self {
+        self.args.insert(k.into(), Property::O(v.into()));
+        self
+    }
+
+    /// Set custom property with its native type, such as [`bool`], [`i64`] or [`f64`].
+    pub fn set_typed<K: Into<String>, V: Into<Property<'static>>>(mut self, k: K, v: V) -> Self {
         self.args.insert(k.into(), v.into());
         self
     }
 
-    #[cfg(any(feature = "toml", feature = "yaml"))]
-    #[cfg_attr(docsrs, doc(cfg(any(feature = "toml", feature = "yaml"))))]
+    #[cfg(any(feature = "toml", feature = "yaml", feature = "properties"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(feature = "toml", feature = "yaml", feature = "properties")))
+    )]
     /// Configure file source.
     pub fn configure_files(mut self, enabled: bool) -> Self {
         self.disable_file = !enabled;
         self
     }
 
+    #[cfg(any(feature = "toml", feature = "yaml", feature = "properties"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(feature = "toml", feature = "yaml", feature = "properties")))
+    )]
+    /// Configure failing to build if an existing configuration file contains no properties,
+    /// such as an empty toml file. Default is disabled, and empty files are ignored.
+    pub fn configure_empty_file_error(mut self, enabled: bool) -> Self {
+        self.deny_empty_file = enabled;
+        self
+    }
+
+    #[cfg(any(feature = "toml", feature = "yaml", feature = "properties"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(feature = "toml", feature = "yaml", feature = "properties")))
+    )]
+    /// Set `salak.app.profile` from process environment variable `name`, such as `APP_ENV`,
+    /// which selects profile files such as `app-dev.toml`. If the variable is absent,
+    /// profile is unchanged. Like [`SalakBuilder::set()`], later settings override it.
+    pub fn set_profile_from_env_var(self, name: &str) -> Self {
+        match std::env::var(name) {
+            Ok(v) => self.set(format!("{}.profile", PREFIX), v),
+            _ => self,
+        }
+    }
+
     #[cfg(feature = "rand")]
     #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
     /// Configure random source.
@@ -69,6 +136,51 @@ impl SalakBuilder {
         self
     }
 
+    /// Disable system environment source.
+    pub fn disable_system_env(mut self) -> Self {
+        self.disable_env = true;
+        self
+    }
+
+    /// Set synthetic environment variables, which are parsed like system environment.
+    /// They are registered right before system environment, so they override variables
+    /// with the same name in system environment.
+    pub fn with_env_map(mut self, vars: HashMap<String, String>) -> Self {
+        self.env_map.get_or_insert_with(HashMap::new).extend(vars);
+        self
+    }
+
+    /// Set synthetic environment variables like [`SalakBuilder::with_env_map()`], and disable
+    /// system environment, so tests using different synthetic environments do not interfere
+    /// with each other.
+    pub fn replace_env_map(self, vars: HashMap<String, String>) -> Self {
+        self.with_env_map(vars).disable_system_env()
+    }
+
+    /// Configure attaching locations of properties to parse errors, such as `app.toml:3`,
+    /// if the source provides [`PropertySource::location()`]. Default is disabled.
+    pub fn configure_error_location(mut self, enabled: bool) -> Self {
+        self.registry.locate = enabled;
+        self
+    }
+
+    /// Configure max depth of placeholders referring to other keys, such as `${b}` in `a`
+    /// referring to `${c}` in `b`. Exceeding the depth fails with [`PropertyError::RecursiveFail`],
+    /// which also stops indirect cycles. Default is `32`.
+    pub fn configure_max_placeholder_depth(mut self, depth: usize) -> Self {
+        self.registry.max_depth = depth;
+        self
+    }
+
+    /// Configure parsing [`Vec`] with missing indices, such as `a[0]` and `a[2]` without `a[1]`.
+    /// By default, parsing stops at the first missing index, so only `a[0]` is parsed. If enabled,
+    /// all indices up to the max one are parsed, and missing ones fail with
+    /// [`PropertyError::NotFound`]. `Vec<Option<T>>` gets `None` for missing indices either way.
+    pub fn configure_sparse_vec(mut self, enabled: bool) -> Self {
+        self.registry.sparse_vec = enabled;
+        self
+    }
+
     #[cfg(feature = "args")]
     #[cfg_attr(docsrs, doc(cfg(feature = "args")))]
     /// Configure predefined arguments.
@@ -83,12 +195,14 @@ impl SalakBuilder {
         #[cfg(feature = "derive")]
         let mut _desc: Vec<KeyDesc> = vec![];
         #[cfg(feature = "derive")]
-        #[cfg(any(feature = "toml", feature = "yaml"))]
+        #[cfg(any(feature = "toml", feature = "yaml", feature = "properties"))]
         {
             self.app_desc
                 .insert(0, Box::new(|env| env.get_desc::<FileConfig>("")));
         }
         let mut env = self.registry;
+        let runtime = Arc::new(RuntimeSource::default());
+        env.register_by_ref(Box::new(runtime.clone()));
 
         #[cfg(feature = "rand")]
         if !self.disable_random {
@@ -96,7 +210,13 @@ impl SalakBuilder {
         }
         let mut salak = Salak {
             reg: env,
-            ior: self.iorefs,
+            ior: Arc::new(self.iorefs),
+            #[cfg(feature = "app")]
+            live: Arc::new(RwLock::new(Arc::new(PropertyRegistryInternal::new("live")))),
+            runtime,
+            cache: Mutex::new(HashMap::new()),
+            hooks: Mutex::new(vec![]),
+            tracked: Mutex::new(HashMap::new()),
             #[cfg(feature = "app")]
             res: self.resource,
         };
@@ -104,9 +224,9 @@ impl SalakBuilder {
         #[cfg(feature = "args")]
         if let Some(app) = self.app_info {
             self.args
-                .insert(format!("{}.name", PREFIX), app.name.into());
+                .insert(format!("{}.name", PREFIX), Property::S(app.name));
             self.args
-                .insert(format!("{}.version", PREFIX), app.version.into());
+                .insert(format!("{}.version", PREFIX), Property::S(app.version));
 
             #[cfg(feature = "derive")]
             {
@@ -130,33 +250,104 @@ impl SalakBuilder {
                 }
             }
 
-            self.args.extend(crate::source::from_args(_desc, app)?);
+            self.args.extend(
+                crate::source::from_args(_desc, app)?
+                    .into_iter()
+                    .map(|(k, v)| (k, Property::O(v))),
+            );
         }
 
         salak.reg = salak
             .reg
-            .register(crate::source::HashMapSource::new("Arguments").set_all(self.args))
-            .register(crate::source::system_environment());
+            .register(crate::source::HashMapSource::new("Arguments").set_all_typed(self.args));
+        if let Some(vars) = self.env_map {
+            salak.reg = salak
+                .reg
+                .register(crate::source_map::environment("SyntheticEnvironment", vars));
+        }
+        if !self.disable_env {
+            salak.reg = salak.reg.register(crate::source::system_environment());
+        }
 
-        #[cfg(any(feature = "toml", feature = "yaml"))]
+        #[cfg(any(feature = "toml", feature = "yaml", feature = "properties"))]
         if !self.disable_file {
             let mut fc = FileConfig::new(&salak.reg, &salak.ior)?;
             #[cfg(feature = "toml")]
             {
-                fc.build("toml", crate::source_toml::Toml::new)?;
+                fc.build("toml", self.deny_empty_file, crate::source_toml::Toml::new)?;
             }
             #[cfg(feature = "yaml")]
             {
-                fc.build("yaml", crate::source_yaml::YamlValue::new)?;
+                fc.build(
+                    "yaml",
+                    self.deny_empty_file,
+                    crate::source_yaml::YamlValue::new,
+                )?;
+            }
+            #[cfg(feature = "properties")]
+            {
+                fc.build(
+                    "properties",
+                    self.deny_empty_file,
+                    crate::source_properties::Properties::new,
+                )?;
             }
             fc.register_to_env(&mut salak.reg);
         }
 
+        #[cfg(feature = "log")]
+        salak.reg.log_sources();
+        #[cfg(feature = "app")]
+        salak.update_live();
+
+        // Resources registered at discovered namespaces may discover more.
+        #[cfg(feature = "app")]
+        while let Some((list_key, register)) = salak.res.next_discovery() {
+            let mut sub_keys = SubKeys::new();
+            salak
+                .reg
+                .get_sub_keys(&Key::from_str(&list_key), &mut sub_keys);
+            let mut namespaces: Vec<String> = sub_keys
+                .str_keys()
+                .into_iter()
+                .map(|v| v.to_string())
+                .collect();
+            namespaces.sort_unstable();
+            for namespace in namespaces {
+                (register)(&mut salak.res, ResourceRegistry::intern(namespace))?;
+            }
+        }
+
         #[cfg(feature = "app")]
-        salak.res.initialize(&salak)?;
+        if !self.defer_init {
+            salak.res.initialize(&salak, self.parallel_init)?;
+        }
         Ok(salak)
     }
 
+    /// Build an immutable [`FrozenSalak`], which never reloads configurations.
+    pub fn freeze(self) -> Res<FrozenSalak> {
+        Ok(self.build()?.freeze())
+    }
+
+    #[cfg(feature = "app")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "app")))]
+    /// Initialize resources with the same [`crate::Ordered`] priority in parallel.
+    /// Resources with different priorities are still initialized in order.
+    pub fn parallel_init(mut self, enabled: bool) -> Self {
+        self.parallel_init = enabled;
+        self
+    }
+
+    #[cfg(feature = "app")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "app")))]
+    /// Defer initializing resources when building, resources should be initialized
+    /// by [`Salak::init_all_resources()`] later.
+    pub fn defer_init(mut self, enabled: bool) -> Self {
+        self.defer_init = enabled;
+        self
+    }
+
     #[inline]
     #[cfg(feature = "app")]
     #[cfg_attr(docsrs, doc(cfg(feature = "app")))]
@@ -178,15 +369,33 @@ impl SalakBuilder {
         Ok(env)
     }
 
+    #[cfg(feature = "app")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "app")))]
+    /// Register [`Resource`] once per sub key under `list_key`, such as namespaces `a` and `b`
+    /// for `redis.instances.a.host` and `redis.instances.b.host`. Sub keys are discovered
+    /// when building, and config of each resource is parsed from `<prefix>.<namespace>`.
+    pub fn register_resources_from<R: Resource + Send + Sync + Any>(
+        mut self,
+        list_key: &str,
+    ) -> Self {
+        self.resource.discover::<R>(list_key);
+        self
+    }
+
     #[inline]
     #[cfg(feature = "app")]
     /// Configure resource description.
     #[cfg_attr(docsrs, doc(cfg(feature = "app")))]
     pub(crate) fn configure_resource_description_by_builder<R: Resource>(
-        self,
+        mut self,
         builder: &ResourceBuilder<R>,
     ) -> Self {
-        self.configure_description_by_namespace::<R::Config>(builder.namespace)
+        let prefix = builder.config_prefix.unwrap_or_else(<R::Config>::prefix);
+        let namespace = builder.namespace;
+        self.app_desc.push(Box::new(move |env| {
+            env.get_desc_by_prefix::<R::Config>(prefix, namespace)
+        }));
+        self
     }
 
     #[cfg(feature = "derive")]
@@ -215,21 +424,48 @@ impl SalakBuilder {
 ///
 #[allow(missing_debug_implementations)]
 pub struct Salak {
-    reg: PropertyRegistryInternal<'static>,
-    ior: Mutex<Vec<Box<dyn IORefT + Send>>>,
+    pub(crate) reg: PropertyRegistryInternal<'static>,
+    pub(crate) ior: Arc<Mutex<Vec<Box<dyn IORefT + Send>>>>,
+    /// Sources currently in use, including sources reloaded last time, shared by
+    /// [`crate::EnvHandle`]s.
+    #[cfg(feature = "app")]
+    pub(crate) live: Arc<RwLock<Arc<PropertyRegistryInternal<'static>>>>,
+    /// Runtime overrides with highest priority.
+    runtime: Arc<RuntimeSource>,
+    cache: Mutex<ValueCache>,
+    hooks: Mutex<Vec<Arc<dyn Fn() + Send + Sync>>>,
+    /// Resolved properties under keys of [`IORefT`]s, updated by reloading.
+    pub(crate) tracked: Mutex<HashMap<String, BTreeMap<String, String>>>,
     #[cfg(feature = "app")]
     pub(crate) res: ResourceRegistry,
 }
 
+/// Report of [`Salak::reload_detailed()`].
+#[derive(Debug, Default)]
+pub struct ReloadReport {
+    /// Properties changed by reloading with old and new values, `None` means absent.
+    /// Only properties under keys of [`crate::wrapper::IORef`]s and watched keys are compared.
+    pub changed: BTreeMap<String, (Option<String>, Option<String>)>,
+    /// Names of reloaded sources.
+    pub sources: Vec<String>,
+}
+
+/// Parsed values cached by type and key.
+type ValueCache = HashMap<(TypeId, String), Arc<dyn Any + Send + Sync>>;
+
 impl Salak {
     /// Create a builder for configure salak env.
     pub fn builder() -> SalakBuilder {
         SalakBuilder {
             args: HashMap::new(),
-            #[cfg(any(feature = "toml", feature = "yaml"))]
+            #[cfg(any(feature = "toml", feature = "yaml", feature = "properties"))]
             disable_file: false,
+            #[cfg(any(feature = "toml", feature = "yaml", feature = "properties"))]
+            deny_empty_file: false,
             #[cfg(feature = "rand")]
             disable_random: false,
+            disable_env: false,
+            env_map: None,
             registry: PropertyRegistryInternal::new("registry"),
             #[cfg(any(feature = "args", feature = "derive"))]
             app_desc: vec![],
@@ -238,7 +474,38 @@ impl Salak {
             iorefs: Mutex::new(vec![]),
             #[cfg(feature = "app")]
             resource: ResourceRegistry::new(),
+            #[cfg(feature = "app")]
+            parallel_init: false,
+            #[cfg(feature = "app")]
+            defer_init: false,
+        }
+    }
+
+    #[cfg(any(feature = "toml", feature = "yaml", feature = "properties"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(feature = "toml", feature = "yaml", feature = "properties")))
+    )]
+    /// Create a builder, and set file configurations from process environment variables.
+    /// * `SALAK_CONFIG_DIR` => `salak.app.dir`, directory of configuration files.
+    /// * `SALAK_CONFIG_FILE` => `salak.app.filename`, file name without extension, default is `app`.
+    ///   It can be overridden by extension, such as `salak.app.filenames.yaml = secrets`.
+    /// * `SALAK_PROFILE` => `salak.app.profile`, profile of configuration files, default is `default`.
+    ///
+    /// These values can be overridden by [`SalakBuilder::set()`], and they override
+    /// the same keys in other sources.
+    pub fn builder_from_env() -> SalakBuilder {
+        let mut builder = Self::builder();
+        for (var, key) in &[
+            ("SALAK_CONFIG_DIR", "dir"),
+            ("SALAK_CONFIG_FILE", "filename"),
+            ("SALAK_PROFILE", "profile"),
+        ] {
+            if let Ok(v) = std::env::var(var) {
+                builder = builder.set(format!("{}.{}", PREFIX, key), v);
+            }
         }
+        builder
     }
 
     /// Create a new salak env.
@@ -249,7 +516,237 @@ impl Salak {
     /// Register source to registry, source that register earlier that higher priority for
     /// configuration.
     pub fn register<P: PropertySource + 'static>(&mut self, provider: P) {
-        self.reg.register_by_ref(Box::new(provider))
+        self.reg.register_by_ref(Box::new(provider));
+        #[cfg(feature = "app")]
+        self.update_live();
+    }
+
+    /// Share sources currently in use with [`crate::EnvHandle`]s.
+    #[cfg(feature = "app")]
+    fn update_live(&self) {
+        *self.live.write() = Arc::new(self.reg.current());
+    }
+
+    /// Convert into an immutable [`FrozenSalak`], which never reloads configurations.
+    /// Registered [`crate::wrapper::IORef`]s, key watchers and reload callbacks are released,
+    /// so receivers of [`Salak::watch_key()`] are disconnected.
+    pub fn freeze(self) -> FrozenSalak {
+        self.ior.lock().clear();
+        self.hooks.lock().clear();
+        self.tracked.lock().clear();
+        FrozenSalak(self)
+    }
+
+    /// Create a scoped view of current environment, keys are prefixed by `prefix`.
+    pub fn scoped(&self, prefix: &str) -> ScopedEnv<'_> {
+        ScopedEnv {
+            env: self,
+            prefix: prefix.to_string(),
+        }
+    }
+
+    /// Parse value of `key` without resolving placeholders, such as a template `${x}` expanded
+    /// later. Escapes such as `\$` are also kept as is.
+    pub fn require_raw<T: IsProperty>(&self, key: &str) -> Res<T> {
+        match self.reg.get_property(&Key::from_str(key)) {
+            Some(v) if !T::is_empty(&v) => T::from_property(v).map_err(|e| match e {
+                PropertyError::ParseFail(None, e) => {
+                    PropertyError::ParseFail(Some(key.to_string()), e)
+                }
+                e => e,
+            }),
+            _ => Err(PropertyError::NotFound(key.to_string())),
+        }
+    }
+
+    /// Override property at runtime, such as feature toggles from an admin api.
+    /// Overrides take precedence over all sources and affect subsequent `require` calls
+    /// immediately, while [`crate::wrapper::IORef`]s are updated on next [`Environment::reload()`].
+    pub fn set_runtime<V: Into<Property<'static>>>(&self, key: &str, val: V) {
+        self.runtime.set(key, val.into());
+        self.cache.lock().clear();
+    }
+
+    /// Parse value of `key` and cache it, the same [`Arc`] is returned for the same type
+    /// and key until [`Environment::reload()`] is called.
+    pub fn require_ref<T>(&self, key: &str) -> Res<Arc<T>>
+    where
+        T: FromEnvironment + Send + Sync + 'static,
+    {
+        let id = (TypeId::of::<T>(), key.to_string());
+        if let Some(v) = self.cache.lock().get(&id) {
+            if let Ok(v) = v.clone().downcast::<T>() {
+                return Ok(v);
+            }
+        }
+        let v = Arc::new(self.require::<T>(key)?);
+        self.cache.lock().insert(id, v.clone());
+        Ok(v)
+    }
+
+    /// Watch value of `key`, the returned [`Receiver`] receives the new value
+    /// whenever [`Environment::reload()`] changes it, current value is not sent.
+    /// If the value fails to parse when reloading, it is skipped without failing the reload,
+    /// and the next valid value different from the last sent one is sent.
+    pub fn watch_key<T>(&self, key: &str) -> Res<Receiver<T>>
+    where
+        T: Clone + PartialEq + FromEnvironment + Send + 'static,
+    {
+        let val = self.require::<T>(key)?;
+        let (sender, receiver) = channel();
+        let mut guard = self.ior.lock();
+        guard.push(Box::new(KeyWatcher::new(key, val, sender)));
+        Ok(receiver)
+    }
+
+    /// Register callback invoked after every successful [`Environment::reload()`],
+    /// no matter whether any value changes. Callbacks are invoked in order of registration.
+    pub fn on_reload(&self, callback: Box<dyn Fn() + Send + Sync>) {
+        self.hooks.lock().push(Arc::from(callback));
+    }
+
+    /// Check whether any source provides `key`, the value is neither parsed
+    /// nor resolved.
+    pub fn contains(&self, key: &str) -> bool {
+        self.reg.contains(&Key::from_str(key))
+    }
+
+    /// Check whether any source provides keys under `prefix`, such as `redis.host`
+    /// under `redis`, or `prefix` itself. Unlike [`Salak::contains()`], nested keys
+    /// count, which tells whether a block is configured without parsing it.
+    pub fn has_any(&self, prefix: &str) -> bool {
+        let key = Key::from_str(prefix);
+        if self.reg.contains(&key) {
+            return true;
+        }
+        let mut sub_keys = SubKeys::new();
+        self.reg.get_sub_keys(&key, &mut sub_keys);
+        !sub_keys.is_empty()
+    }
+
+    /// Dump all resolved configurations as flat keys, placeholders are expanded.
+    /// Keys failing to resolve are omitted.
+    pub fn dump_effective_config(&self) -> BTreeMap<String, String> {
+        self.dump_effective_config_with(|_| false)
+    }
+
+    /// Dump all resolved configurations as flat keys, values of keys matching `redact`
+    /// are replaced by `******`.
+    pub fn dump_effective_config_with(
+        &self,
+        redact: impl Fn(&str) -> bool,
+    ) -> BTreeMap<String, String> {
+        let mut keys = vec![];
+        self.reg.keys(&mut Key::new(), &mut keys);
+        let mut map = BTreeMap::new();
+        for key in keys {
+            if redact(&key) {
+                map.insert(key, "******".to_string());
+            } else if let Ok(Some(v)) = self.require::<Option<String>>(&key) {
+                map.insert(key, v);
+            }
+        }
+        map
+    }
+
+    /// Compare resolved configurations with `other`, keys with different values are returned
+    /// with values of both sides, `None` means absent.
+    pub fn diff(&self, other: &Salak) -> BTreeMap<String, (Option<String>, Option<String>)> {
+        self.diff_with(other, |_| false)
+    }
+
+    /// Compare resolved configurations with `other`, values of keys matching `redact` are
+    /// replaced by `******`, and only presence of them is compared.
+    pub fn diff_with(
+        &self,
+        other: &Salak,
+        redact: impl Fn(&str) -> bool,
+    ) -> BTreeMap<String, (Option<String>, Option<String>)> {
+        diff(
+            self.dump_effective_config_with(&redact),
+            other.dump_effective_config_with(&redact),
+        )
+    }
+
+    /// Reload configuration like [`Environment::reload()`], and report changed properties
+    /// and reloaded sources.
+    pub fn reload_detailed(&self) -> Res<ReloadReport> {
+        self.reload_with(true)
+    }
+
+    /// Reload sources, properties are only compared if `detailed`. Plain reloading
+    /// only keeps properties tracked by earlier detailed reloading up to date.
+    fn reload_with(&self, detailed: bool) -> Res<ReloadReport> {
+        self.cache.lock().clear();
+        let mut tracked = self.tracked.lock();
+        let keys: BTreeSet<String> = self
+            .ior
+            .lock()
+            .iter()
+            .map(|io| io.key())
+            .filter(|key| detailed || tracked.contains_key(*key))
+            .map(|key| key.to_string())
+            .collect();
+        let mut before = BTreeMap::new();
+        let mut after = BTreeMap::new();
+        if detailed {
+            for key in &keys {
+                match tracked.get(key) {
+                    Some(v) => before.extend(v.clone()),
+                    _ => before.extend(self.reg.snapshot(key)),
+                }
+            }
+        }
+        let sources = self.reg.reload(&self.ior, |registry| {
+            #[cfg(feature = "app")]
+            self.update_live();
+            #[cfg(feature = "app")]
+            self.res.reload(self, registry)?;
+            for key in keys {
+                let v = registry.snapshot(&key);
+                if detailed {
+                    after.extend(v.clone());
+                }
+                tracked.insert(key, v);
+            }
+            Ok(())
+        })?;
+        // Keys no longer tracked, such as keys of dropped watchers, are removed.
+        let keys: BTreeSet<String> = self
+            .ior
+            .lock()
+            .iter()
+            .map(|io| io.key().to_string())
+            .collect();
+        tracked.retain(|key, _| keys.contains(key));
+        drop(tracked);
+        // Hooks are called without holding the lock, so they can register hooks.
+        let hooks = self.hooks.lock().clone();
+        for hook in hooks {
+            (hook)();
+        }
+        Ok(ReloadReport {
+            changed: diff(before, after),
+            sources,
+        })
+    }
+
+    /// Reconstruct configurations under `prefix` as [`serde_json::Value`], indexed keys
+    /// are converted to arrays, and placeholders are expanded.
+    /// Return [`serde_json::Value::Null`] if no configuration found.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn require_json_value(&self, prefix: &str) -> Res<serde_json::Value> {
+        Ok(self
+            .reg
+            .json_value(&mut Key::from_str(prefix))?
+            .unwrap_or(serde_json::Value::Null))
+    }
+
+    /// Expand placeholders in `val`, such as `${app.name}.server`.
+    #[cfg(feature = "derive")]
+    pub(crate) fn resolve_placeholder(&self, val: &str) -> Res<String> {
+        self.reg.resolve_placeholder(val)
     }
 
     #[cfg(feature = "derive")]
@@ -258,14 +755,35 @@ impl Salak {
     pub(crate) fn get_desc<T: PrefixedFromEnvironment + DescFromEnvironment>(
         &self,
         namespace: &'static str,
+    ) -> Vec<KeyDesc> {
+        self.get_desc_by_prefix::<T>(T::prefix(), namespace)
+    }
+
+    #[cfg(feature = "derive")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+    /// Describe keys of `T` under `namespace`, such as `postgresql.replica.host` for
+    /// namespace `replica`, so each instance of a multi-instance config can be documented.
+    pub fn describe_namespaced<T: PrefixedFromEnvironment + DescFromEnvironment>(
+        &self,
+        namespace: &str,
+    ) -> Vec<KeyDesc> {
+        self.get_desc_by_prefix::<T>(T::prefix(), namespace)
+    }
+
+    #[cfg(feature = "derive")]
+    /// Get key description under `prefix`.
+    pub(crate) fn get_desc_by_prefix<T: DescFromEnvironment>(
+        &self,
+        prefix: &str,
+        namespace: &str,
     ) -> Vec<KeyDesc> {
         let mut key = Key::new();
         let mut key_descs = vec![];
         let mut context = SalakDescContext::new(&mut key, &mut key_descs);
         if namespace.is_empty() {
-            context.add_key_desc::<T>(T::prefix(), None, None, None);
+            context.add_key_desc::<T>(prefix, None, None, None);
         } else {
-            context.add_key_desc::<T>(&format!("{}.{}", T::prefix(), namespace), None, None, None);
+            context.add_key_desc::<T>(&format!("{}.{}", prefix, namespace), None, None, None);
         };
         key_descs
     }
@@ -274,11 +792,143 @@ impl Salak {
 impl Environment for Salak {
     #[inline]
     fn reload(&self) -> Res<bool> {
-        self.reg.reload(&self.ior)
+        Ok(!self.reload_with(false)?.sources.is_empty())
     }
 
     #[inline]
     fn require<T: FromEnvironment>(&self, key: &str) -> Res<T> {
         self.reg.require(key, &self.ior)
     }
+
+    #[cfg(feature = "derive")]
+    #[inline]
+    fn get<T: PrefixedFromEnvironment>(&self) -> Res<T> {
+        self.require::<T>(&T::prefix_resolved(self)?)
+    }
+}
+
+/// Properties with different values in `left` and `right`, `None` means absent.
+fn diff(
+    left: BTreeMap<String, String>,
+    right: BTreeMap<String, String>,
+) -> BTreeMap<String, (Option<String>, Option<String>)> {
+    let keys: BTreeSet<&String> = left.keys().chain(right.keys()).collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            let l = left.get(key).cloned();
+            let r = right.get(key).cloned();
+            if l == r {
+                None
+            } else {
+                Some((key.clone(), (l, r)))
+            }
+        })
+        .collect()
+}
+
+/// An immutable [`Salak`] created by [`SalakBuilder::freeze()`] or [`Salak::freeze()`],
+/// configurations are guaranteed not to change.
+///
+/// [`Environment::reload()`] is a no-op, so sources are never reloaded and
+/// [`crate::wrapper::IORef`]s are never updated. Sources can not be registered either.
+///
+/// ```compile_fail
+/// use salak::{source::HashMapSource, *};
+/// let mut env = Salak::builder().freeze().unwrap();
+/// env.register(HashMapSource::new("map"));
+/// ```
+#[allow(missing_debug_implementations)]
+pub struct FrozenSalak(Salak);
+
+impl Environment for FrozenSalak {
+    /// Configurations are frozen, always returns `false`.
+    #[inline]
+    fn reload(&self) -> Res<bool> {
+        Ok(false)
+    }
+
+    #[inline]
+    fn require<T: FromEnvironment>(&self, key: &str) -> Res<T> {
+        self.0.require(key)
+    }
+
+    #[cfg(feature = "derive")]
+    #[inline]
+    fn get<T: PrefixedFromEnvironment>(&self) -> Res<T> {
+        self.0.get()
+    }
+}
+
+/// A scoped view of [`Salak`], which prepends prefix to keys.
+#[allow(missing_debug_implementations)]
+pub struct ScopedEnv<'a> {
+    env: &'a Salak,
+    prefix: String,
+}
+
+impl<'a> ScopedEnv<'a> {
+    /// Create a nested scoped view, prefixes are composed.
+    pub fn scoped(&self, prefix: &str) -> ScopedEnv<'a> {
+        ScopedEnv {
+            env: self.env,
+            prefix: self.key(prefix),
+        }
+    }
+
+    /// Get current prefix.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    fn key(&self, key: &str) -> String {
+        if self.prefix.is_empty() {
+            key.to_string()
+        } else if key.is_empty() {
+            self.prefix.clone()
+        } else if key.starts_with('[') {
+            format!("{}{}", self.prefix, key)
+        } else {
+            format!("{}.{}", self.prefix, key)
+        }
+    }
+}
+
+impl Environment for ScopedEnv<'_> {
+    #[inline]
+    fn reload(&self) -> Res<bool> {
+        self.env.reload()
+    }
+
+    #[inline]
+    fn require<T: FromEnvironment>(&self, key: &str) -> Res<T> {
+        self.env.require(&self.key(key))
+    }
+}
+
+#[cfg(all(test, feature = "log"))]
+mod tests {
+    use crate::{source::HashMapSource, test_log::*, *};
+
+    #[test]
+    fn log_sources_test() {
+        init();
+        let _ = Salak::builder().set("a", "1").build().unwrap();
+        assert!(any_log(log::Level::Debug, |v| {
+            match (v.find(". Arguments, "), v.find(". SystemEnvironment")) {
+                (Some(args), Some(env)) => args < env,
+                _ => false,
+            }
+        }));
+
+        let mut registry = PropertyRegistryInternal::new("test");
+        registry.register_by_ref(Box::new(HashMapSource::new("first").set("a", "1")));
+        registry.register_by_ref(Box::new(HashMapSource::new("second")));
+        registry.log_sources();
+        assert!(any_log(log::Level::Debug, |v| {
+            v == "Sources in order of precedence: 1. first, 2. second (empty)."
+        }));
+        assert!(any_log(log::Level::Debug, |v| {
+            v == "Source second is empty, pruned."
+        }));
+    }
 }
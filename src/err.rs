@@ -1,4 +1,13 @@
-use std::{error::Error, fmt::Display};
+#[cfg(all(feature = "core-parse", not(feature = "std")))]
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+};
+#[cfg(all(feature = "core-parse", not(feature = "std")))]
+use core::error::Error;
+use core::fmt::Display;
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
+use std::error::Error;
 
 #[allow(unused_imports)]
 use crate::*;
@@ -16,7 +25,9 @@ pub enum PropertyError {
     RecursiveFail(String),
     /// [`Property`] not found
     NotFound(String),
-    /// Resource not found
+    /// Resource not found, namespace and type name of resource.
+    /// Resource is not registered under the namespace, or failed to initialize.
+    /// Use `Salak::resource_namespaces()` to check where the resource type is registered.
     ResourceNotFound(&'static str, &'static str),
     /// Resource already registered.
     ResourceRegistered(&'static str, &'static str),
@@ -30,13 +41,43 @@ pub(crate) struct SalakParseError(String);
 
 impl Display for SalakParseError {
     #[inline]
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_str(&self.0)
     }
 }
 
 impl Error for SalakParseError {}
 
+/// Parse error with location where the property is defined.
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
+#[derive(Debug)]
+pub(crate) struct LocatedError {
+    location: String,
+    err: Box<dyn Error>,
+}
+
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
+impl LocatedError {
+    pub(crate) fn new(location: String, err: Box<dyn Error>) -> Self {
+        Self { location, err }
+    }
+}
+
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
+impl Display for LocatedError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}, at {}", self.err, self.location)
+    }
+}
+
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
+impl Error for LocatedError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(self.err.as_ref())
+    }
+}
+
 impl PropertyError {
     /// Create parse fail error.
     #[inline]
@@ -45,9 +86,179 @@ impl PropertyError {
     }
 }
 
+impl PropertyError {
+    /// Get the underlying error of [`PropertyError::ParseFail`], location attached by
+    /// [`SalakBuilder::configure_error_location()`] is skipped. Use `downcast_ref` to
+    /// check the error type, eg. `err.parse_cause()?.downcast_ref::<ParseIntError>()`.
+    pub fn parse_cause(&self) -> Option<&(dyn Error + 'static)> {
+        let e = match self {
+            PropertyError::ParseFail(_, e) => e,
+            _ => return None,
+        };
+        #[cfg(any(feature = "std", not(feature = "core-parse")))]
+        if let Some(e) = e.downcast_ref::<LocatedError>() {
+            return Some(e.err.as_ref());
+        }
+        Some(e.as_ref())
+    }
+}
+
+impl Display for PropertyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PropertyError::ParseFail(Some(key), err) => write!(f, "parse {} failed: {}", key, err),
+            PropertyError::ParseFail(None, err) => write!(f, "{}", err),
+            PropertyError::ResolveFail(key) => write!(f, "resolve {} failed", key),
+            PropertyError::ResolveNotFound(key) => write!(f, "placeholder {} not found", key),
+            PropertyError::RecursiveFail(key) => write!(f, "placeholder {} is recursive", key),
+            PropertyError::NotFound(key) => write!(f, "property {} not found", key),
+            PropertyError::ResourceNotFound(namespace, name) => {
+                write!(f, "resource {} not found at [{}]", name, namespace)
+            }
+            PropertyError::ResourceRegistered(namespace, name) => {
+                write!(f, "resource {} already registered at [{}]", name, namespace)
+            }
+            PropertyError::ResourceRecursive(namespace, name) => {
+                write!(f, "resource {} at [{}] is recursive", name, namespace)
+            }
+        }
+    }
+}
+
 impl<E: Error + 'static> From<E> for PropertyError {
     #[inline]
     fn from(err: E) -> Self {
         PropertyError::ParseFail(None, Box::new(err))
     }
 }
+
+/// [`PropertyError`] sent back from other threads, such as threads initializing resources.
+/// The cause of [`PropertyError::ParseFail`] may not be [`Send`], so only its message is sent.
+#[cfg(feature = "app")]
+pub(crate) struct SendError(Box<dyn FnOnce() -> PropertyError + Send>);
+
+#[cfg(feature = "app")]
+impl SendError {
+    pub(crate) fn into_error(self) -> PropertyError {
+        (self.0)()
+    }
+}
+
+#[cfg(feature = "app")]
+impl From<PropertyError> for SendError {
+    fn from(err: PropertyError) -> Self {
+        SendError(match err {
+            PropertyError::ParseFail(key, e) => {
+                let msg = SalakParseError(e.to_string());
+                Box::new(move || PropertyError::ParseFail(key, Box::new(msg)))
+            }
+            PropertyError::ResolveFail(v) => Box::new(move || PropertyError::ResolveFail(v)),
+            PropertyError::ResolveNotFound(v) => {
+                Box::new(move || PropertyError::ResolveNotFound(v))
+            }
+            PropertyError::RecursiveFail(v) => Box::new(move || PropertyError::RecursiveFail(v)),
+            PropertyError::NotFound(v) => Box::new(move || PropertyError::NotFound(v)),
+            PropertyError::ResourceNotFound(n, t) => {
+                Box::new(move || PropertyError::ResourceNotFound(n, t))
+            }
+            PropertyError::ResourceRegistered(n, t) => {
+                Box::new(move || PropertyError::ResourceRegistered(n, t))
+            }
+            PropertyError::ResourceRecursive(n, t) => {
+                Box::new(move || PropertyError::ResourceRecursive(n, t))
+            }
+        })
+    }
+}
+
+/// Convert into [`std::io::Error`], the original io error is returned if `err`
+/// is converted from it.
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
+impl From<PropertyError> for std::io::Error {
+    fn from(err: PropertyError) -> Self {
+        use std::io::ErrorKind;
+        let err = match err {
+            PropertyError::ParseFail(None, e) => {
+                return match e.downcast::<std::io::Error>() {
+                    Ok(e) => *e,
+                    Err(e) => std::io::Error::new(ErrorKind::InvalidData, e.to_string()),
+                }
+            }
+            err => err,
+        };
+        let kind = match &err {
+            PropertyError::ParseFail(_, _)
+            | PropertyError::ResolveFail(_)
+            | PropertyError::ResolveNotFound(_)
+            | PropertyError::RecursiveFail(_) => ErrorKind::InvalidData,
+            PropertyError::NotFound(_) | PropertyError::ResourceNotFound(_, _) => {
+                ErrorKind::NotFound
+            }
+            PropertyError::ResourceRegistered(_, _) => ErrorKind::AlreadyExists,
+            PropertyError::ResourceRecursive(_, _) => ErrorKind::Other,
+        };
+        std::io::Error::new(kind, err.to_string())
+    }
+}
+
+#[cfg(all(test, any(feature = "std", not(feature = "core-parse"))))]
+mod tests {
+    use crate::{err::LocatedError, *};
+    use std::io::{Error, ErrorKind};
+
+    #[test]
+    fn io_error_test() {
+        let err: PropertyError = Error::new(ErrorKind::PermissionDenied, "denied").into();
+        assert_eq!("denied", err.to_string());
+        let err: Error = err.into();
+        assert_eq!(ErrorKind::PermissionDenied, err.kind());
+        assert_eq!("denied", err.to_string());
+
+        let err: Error = PropertyError::NotFound("a.b".to_string()).into();
+        assert_eq!(ErrorKind::NotFound, err.kind());
+        assert_eq!("property a.b not found", err.to_string());
+
+        let err: Error = PropertyError::parse_fail("bad value").into();
+        assert_eq!(ErrorKind::InvalidData, err.kind());
+        assert_eq!("bad value", err.to_string());
+
+        let err: Error = PropertyError::ResourceRegistered("", "A").into();
+        assert_eq!(ErrorKind::AlreadyExists, err.kind());
+
+        fn read() -> std::io::Result<u8> {
+            Ok(Salak::new()?.require::<u8>("not.exists")?)
+        }
+        assert_eq!(ErrorKind::NotFound, read().unwrap_err().kind());
+    }
+
+    #[test]
+    fn parse_cause_test() {
+        use std::num::ParseIntError;
+        let err: PropertyError = "x".parse::<u8>().unwrap_err().into();
+        assert!(err
+            .parse_cause()
+            .and_then(|e| e.downcast_ref::<ParseIntError>())
+            .is_some());
+        assert!(PropertyError::NotFound("a".to_string())
+            .parse_cause()
+            .is_none());
+
+        let env = Salak::builder().set("a", "x").build().unwrap();
+        let err = env.require::<u8>("a").unwrap_err();
+        assert!(err.parse_cause().unwrap().is::<ParseIntError>());
+
+        let located = LocatedError::new(
+            "app.toml:1".to_string(),
+            Box::new("x".parse::<u8>().unwrap_err()),
+        );
+        let err = PropertyError::ParseFail(Some("a".to_string()), Box::new(located));
+        assert!(err.parse_cause().unwrap().is::<ParseIntError>());
+
+        let err = PropertyError::parse_fail("bad");
+        assert!(err
+            .parse_cause()
+            .unwrap()
+            .downcast_ref::<ParseIntError>()
+            .is_none());
+    }
+}
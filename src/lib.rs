@@ -61,10 +61,16 @@
 //!    * `random.i128`
 //!    * `random.isize`
 //! 2. Custom arguments source. [`SalakBuilder::set()`] can set a single kv,
-//! and [`SalakBuilder::set_args()`] can set a group of kvs.
-//! 3. System environment source. Implemented by [`source::system_environment`].
+//!    and [`SalakBuilder::set_args()`] or [`SalakBuilder::set_all()`] can set a group of kvs.
+//! 3. System environment source. Implemented by [`source::system_environment`], variables such as `servers__0`
+//!    are also used as key `servers[0]`.
 //! 4. Profile specified file source, eg. `app-dev.toml`, supports reloading.
 //! 5. No profile file source, eg. `app.toml`, supports reloading.
+//!    File sources can include other files by key `salak.include`, eg. `salak.include = ["db.toml"]`,
+//!    paths are relative to the including file, and included files have lower priority.
+//!    Java style `app.properties` files are also supported by feature `properties`.
+//!    File name is configured by `salak.app.filename`, and can be overridden by extension,
+//!    eg. `salak.app.filenames.yaml = secrets` loads `secrets.yaml` but still `app.toml`.
 //! 6. Custom sources, which can register by [`Salak::register()`].
 //!
 //! #### Key Convention
@@ -86,6 +92,7 @@
 //! 1. Placeholder Format
 //!    * `${key}` => Get value of `key`.
 //!    * `${key:default}` => Get value of `key`, if not exists return `default`.
+//!      Default can contain `:`, eg. `${db.url:postgres://localhost:5432/db}`.
 //! 2. Escape Format
 //!    * `\$\{key\}` => Return `${key}`.
 //!    * `$`, `\`, `{`, `}` must use escape format.
@@ -93,24 +100,50 @@
 //! #### Attributes For Derive
 //! `salak` supports some attributes for automatically derive [`FromEnvironment`].
 //! All attributes have format `#[salak(..)]`, eg. `#[salak(default = "default value")]`.
-//! 1. Struct Header Attribute.
+//! 1. Struct/Enum Header Attribute.
 //!    * `#[salak(prefix = "salak.application")]`, has this attr will auto implement [`PrefixedFromEnvironment`].
+//!      Placeholders in prefix such as `${app.name}.server` are resolved when getting values.
+//!      For type with hand-written [`FromEnvironment`], use `#[derive(Prefixed)]` with this attr to only implement [`PrefixedFromEnvironment`].
+//!      For type with hand-written [`FromEnvironment`] and no prefix, use `#[derive(DescFromEnvironment)]` to describe it as a single property.
+//!    * `#[salak(numeric_only)]`, enum only, this attr will only accept discriminant values such as `2`,
+//!      by default both variant names and discriminant values are accepted.
 //! 2. Struct Field Attribute.
-//!    * `#[salak(default = "value")]`, this attr can specify default value.
+//!    * `#[salak(default = "value")]`, this attr can specify default value. Defaults of primitive number and bool
+//!      fields are checked at compile time, others are checked when parsing.
 //!    * `#[salak(name = "key")]`, this attr can specify property key, default convension is use field name.
 //!    * `#[salak(desc = "Field Description")]`, this attr can be describe this property.
+//!    * `#[salak(deprecated = "use other key")]`, this attr will log a warning if the property is set.
+//!    * `#[salak(alias = "old_key")]`, this attr can specify alias keys, which are tried in order before the key.
+//!    * `#[salak(min_len = 8)]`, `#[salak(max_len = 5)]`, these attrs check `len()` of value, such as bytes of
+//!      [`String`] or elements of [`Vec`]. `None` of [`Option`] is not checked.
+//!    * `#[salak(trim)]`, this attr trims whitespaces of string value before parsing, whitespace only value
+//!      is treated as not found, so default value is used or `None` for [`Option`].
+//!    * `#[salak(required)]`, this attr makes [`Option`] field fail to load if property is not found,
+//!      instead of returning `None`. It can not be used together with `default`.
+//! 3. Enum Variant Attribute.
+//!    * `#[salak(name = "value")]`, this attr can specify property value, default convension is use lowercase variant name.
 //!
 //! #### Reload Configuration
 //! `salak` supports reload configurations. Since in rust mutable
 //! and alias can't be used together, here we introduce a wrapper
 //! [`wrapper::IORef`] for updating values when reloading.
+//! [`Salak::reload_detailed()`] reports properties of [`wrapper::IORef`]s changed by reloading.
 //!
 //! #### Resource Factory
 //! [`Resource`] defines a standard way to create instance. [`Factory`] provides functions to initialize resource
 //! and cache resource. Please refer to [salak_factory](https://docs.rs/salak_factory) for resource usage.
 //! Feature 'app' should be open for this feature.
 //!
+//! #### Core Parsing
+//! Feature 'core-parse' builds only [`Property`], [`IsProperty`] and [`PropertyError`], which require
+//! `alloc` only, when feature 'std' is disabled, eg. `--no-default-features --features core-parse`.
+//! Otherwise the whole crate is built as before. [`IsProperty`] of [`String`],
+//! `Cow<'static, str>`, [`bool`], numbers and nonzero numbers are available, while net types,
+//! `PathBuf`, `OsString` and `Duration` require feature 'std'. `SystemTime` is parsed from unix
+//! epoch seconds, and from RFC3339 time with feature 'chrono'.
+//!
 #![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(all(feature = "core-parse", not(feature = "std")), no_std)]
 #![warn(
     anonymous_parameters,
     missing_copy_implementations,
@@ -127,26 +160,49 @@
     variant_size_differences
 )]
 
+#[cfg(all(
+    feature = "core-parse",
+    not(feature = "std"),
+    any(feature = "rand", feature = "ipnet", feature = "log")
+))]
+compile_error!("features `rand`, `ipnet` and `log` require feature `std` with `core-parse`");
+
+#[cfg(all(feature = "core-parse", not(feature = "std")))]
+extern crate alloc;
+
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 use parking_lot::Mutex;
 
-#[cfg(feature = "derive")]
-use crate::derive::KeyDesc;
 #[cfg(feature = "derive")]
 mod derive;
 #[cfg(feature = "derive")]
 #[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
 pub use crate::derive::{
-    AutoDeriveFromEnvironment, DescFromEnvironment, PrefixedFromEnvironment, SalakDescContext,
+    AutoDeriveFromEnvironment, DescFromEnvironment, KeyDesc, PrefixedFromEnvironment,
+    SalakDescContext,
 };
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 use raw_ioref::IORefT;
+/// Auto derive [`DescFromEnvironment`] for type with hand-written [`FromEnvironment`],
+/// describing it as a single property.
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use salak_derive::DescFromEnvironment;
 /// Auto derive [`FromEnvironment`] for struct.
 #[cfg(feature = "derive")]
 #[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
 pub use salak_derive::FromEnvironment;
+/// Auto derive [`PrefixedFromEnvironment`] for type with hand-written [`FromEnvironment`].
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+pub use salak_derive::Prefixed;
 /// Auto derive [`Service`] for struct.
 #[cfg(all(feature = "derive", feature = "app"))]
 #[cfg_attr(docsrs, doc(cfg(all(feature = "derive", feature = "app"))))]
 pub use salak_derive::Service;
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
+pub use source_raw::KeyGuard;
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 use source_raw::PropertyRegistryInternal;
 
 #[cfg(feature = "args")]
@@ -157,23 +213,51 @@ mod args;
 pub use crate::args::AppInfo;
 
 mod err;
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 mod raw;
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
+mod raw_addr;
+#[cfg(feature = "ipnet")]
+mod raw_cidr;
+mod raw_core;
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
+mod raw_file;
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
+mod raw_path;
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
+mod raw_size;
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 use crate::raw::SubKey;
-pub use crate::raw::{IsProperty, Property};
+pub use crate::raw_core::{IsProperty, Property};
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 mod raw_ioref;
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 mod raw_vec;
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 use crate::env::PREFIX;
-pub use crate::env::{Salak, SalakBuilder};
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
+pub use crate::env::{FrozenSalak, ReloadReport, Salak, SalakBuilder, ScopedEnv};
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 mod env;
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 mod raw_enum;
 
 pub use crate::err::PropertyError;
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 pub use crate::raw_enum::EnumProperty;
 
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+mod source_json;
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 mod source_map;
+#[cfg(feature = "properties")]
+#[cfg_attr(docsrs, doc(cfg(feature = "properties")))]
+mod source_properties;
 #[cfg(feature = "rand")]
 #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
 mod source_rand;
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 mod source_raw;
 #[cfg(feature = "toml")]
 #[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
@@ -182,7 +266,9 @@ mod source_toml;
 #[cfg_attr(docsrs, doc(cfg(feature = "yaml")))]
 mod source_yaml;
 
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 use crate::source::Key;
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 use crate::source::SubKeys;
 
 #[cfg(feature = "app")]
@@ -192,34 +278,64 @@ mod app;
 #[cfg_attr(docsrs, doc(cfg(feature = "app")))]
 pub use crate::app::*;
 
+#[cfg(feature = "test-util")]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+pub mod testing;
+
 #[cfg(test)]
 #[macro_use(quickcheck)]
 extern crate quickcheck_macros;
 
+#[cfg(all(test, feature = "log"))]
+mod test_log;
+
+/// Serialize tests mutating process environment variables.
+#[cfg(all(test, any(feature = "std", not(feature = "core-parse"))))]
+pub(crate) fn lock_env() -> std::sync::MutexGuard<'static, ()> {
+    static ENV: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    ENV.lock().unwrap_or_else(|e| e.into_inner())
+}
+
 /// Salak wrapper for configuration parsing.
 ///
 /// Wrapper can determine extra behavior for parsing.
 /// Such as check empty of vec or update when reloading.
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 pub mod wrapper {
+    pub use crate::raw_addr::AddrWithDefaultPort;
+    #[cfg(feature = "ipnet")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "ipnet")))]
+    pub use crate::raw_cidr::Cidr;
+    pub use crate::raw_file::FromFile;
     pub use crate::raw_ioref::IORef;
+    pub use crate::raw_path::ExpandedPath;
+    pub use crate::raw_size::ByteSize;
     pub use crate::raw_vec::NonEmptyVec;
 }
 
 /// Salak sources.
 ///
 /// This mod exports all pub sources.
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 pub mod source {
 
     #[cfg(feature = "args")]
     #[cfg_attr(docsrs, doc(cfg(feature = "args")))]
     pub(crate) use crate::args::from_args;
     pub use crate::raw::Key;
+    pub use crate::raw::Segment;
     pub use crate::raw::SubKeys;
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub use crate::source_json::JsonSource;
     pub use crate::source_map::system_environment;
     pub use crate::source_map::HashMapSource;
+    pub use crate::source_map::OrSource;
+    pub use crate::source_map::SharedMapSource;
 }
 
 pub(crate) type Res<T> = Result<T, PropertyError>;
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 pub(crate) type Void = Res<()>;
 
 /// A property source defines how to load properties.
@@ -232,6 +348,8 @@ pub(crate) type Void = Res<()>;
 /// * std::env source
 /// * toml source
 /// * yaml source
+/// * properties source
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 pub trait PropertySource: Send + Sync {
     /// [`PropertySource`] name.
     fn name(&self) -> &str;
@@ -246,6 +364,13 @@ pub trait PropertySource: Send + Sync {
     /// Key and SubKeys.
     fn get_sub_keys<'a>(&'a self, key: &Key<'_>, sub_keys: &mut SubKeys<'a>);
 
+    /// Check whether the source provides property of `key`. Default is checking by
+    /// [`PropertySource::get_property()`], sources can override it with a cheaper check.
+    #[inline]
+    fn contains(&self, key: &Key<'_>) -> bool {
+        self.get_property(key).is_some()
+    }
+
     /// Check whether the [`PropertySource`] is empty.
     /// Empty source will be ignored when registering to `salak`.
     fn is_empty(&self) -> bool;
@@ -255,12 +380,49 @@ pub trait PropertySource: Send + Sync {
     fn reload_source(&self) -> Res<Option<Box<dyn PropertySource>>> {
         Ok(None)
     }
+
+    /// Minimum interval between two reloads of this source, reloading within
+    /// the interval reuses the last reloaded source. Default is none, which means
+    /// reloading every time.
+    #[inline]
+    fn reload_interval(&self) -> Option<std::time::Duration> {
+        None
+    }
+
+    /// Location where property of `key` is defined, such as `app.toml:3`, which is
+    /// attached to parse errors if [`SalakBuilder::configure_error_location()`] is enabled.
+    /// Default is none.
+    #[inline]
+    fn location(&self, _key: &Key<'_>) -> Option<String> {
+        None
+    }
+
+    /// Get subtree under `key` as structured value, so it can be consumed directly without
+    /// reconstructing from flattened properties, such as [`serde_json::Value`] by
+    /// [`Salak::require_json_value()`]. Default is none, which means flattened only.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    #[inline]
+    fn get_json<'a>(&'a self, _key: &Key<'_>) -> Option<&'a serde_json::Value> {
+        None
+    }
+
+    /// Combine with `other` source, properties are get from this source first,
+    /// then `other`, and sub keys are merged. The combined source can be registered
+    /// as a unit.
+    fn or<B: PropertySource>(self, other: B) -> source::OrSource<Self, B>
+    where
+        Self: Sized,
+    {
+        source::OrSource::new(self, other)
+    }
 }
 
 /// Environment defines interface for getting values, and reloading
 /// configurations.
 ///
 /// The implementor of this trait is [`Salak`].
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 pub trait Environment {
     /// Get value by key.
     /// * `key` - Configuration key.
@@ -270,6 +432,57 @@ pub trait Environment {
     /// `Option<T>`, then not found will return `None`.
     fn require<T: FromEnvironment>(&self, key: &str) -> Res<T>;
 
+    /// Get value by key, then transform it by `f`.
+    /// * `key` - Configuration key.
+    /// * `f` - Post-processing function, such as trim or lowercase.
+    ///
+    /// Parse failures returned by `f` will be attached with `key`.
+    #[inline]
+    fn require_with<T: FromEnvironment, U>(&self, key: &str, f: impl Fn(T) -> Res<U>) -> Res<U> {
+        f(self.require::<T>(key)?).map_err(|e| match e {
+            PropertyError::ParseFail(None, e) => PropertyError::ParseFail(Some(key.to_string()), e),
+            e => e,
+        })
+    }
+
+    /// Get value by key, then convert it into `U` by [`From`], such as a newtype of `T`.
+    /// * `key` - Configuration key.
+    #[inline]
+    fn require_into<T: FromEnvironment, U: From<T>>(&self, key: &str) -> Res<U> {
+        self.require::<T>(key).map(U::from)
+    }
+
+    /// Get value by key, then split it by `delimiter` and parse each element as `T`,
+    /// such as `a,b,c` or `1;2;3`. Elements are trimmed, and empty value yields empty vec.
+    /// Empty elements, such as the second one of `a,,b`, are missing values, so they are
+    /// `None` for `Option<T>`, and other types fail with [`PropertyError::NotFound`].
+    /// * `key` - Configuration key.
+    /// * `delimiter` - Delimiter of elements.
+    ///
+    /// Parse failures of elements will be attached with `key`.
+    #[inline]
+    fn require_vec_csv<T: FromEnvironment>(&self, key: &str, delimiter: char) -> Res<Vec<T>> {
+        self.require_with::<String, _>(key, |v| source_raw::parse_delimited(key, &v, delimiter))
+    }
+
+    /// Get duration by key, if not found then parse `def`, such as `5s` or `100ms`.
+    /// * `key` - Configuration key.
+    /// * `def` - Default duration, it is parsed only if `key` is not found.
+    ///
+    /// Parse failures of `def` will be attached with `key`.
+    #[inline]
+    fn require_duration(&self, key: &str, def: &str) -> Res<std::time::Duration> {
+        match self.require::<Option<std::time::Duration>>(key)? {
+            Some(v) => Ok(v),
+            _ => std::time::Duration::from_property(Property::S(def)).map_err(|e| match e {
+                PropertyError::ParseFail(None, e) => {
+                    PropertyError::ParseFail(Some(key.to_string()), e)
+                }
+                e => e,
+            }),
+        }
+    }
+
     /// Reload configuration. If reloading is completed,
     /// all values wrapped by [`wrapper::IORef`] will be updated.
     ///
@@ -291,6 +504,7 @@ pub trait Environment {
 }
 
 /// Context for implementing [`FromEnvironment`].
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 #[allow(missing_debug_implementations)]
 pub struct SalakContext<'a> {
     registry: &'a PropertyRegistryInternal<'a>,
@@ -299,6 +513,7 @@ pub struct SalakContext<'a> {
 }
 
 /// Parsing value from environment by [`SalakContext`].
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
 pub trait FromEnvironment: Sized {
     /// Generate object from [`SalakContext`].
     /// * `val` - Property value can be parsed from.
@@ -1,49 +1,16 @@
 #[cfg(feature = "derive")]
 use crate::{DescFromEnvironment, PrefixedFromEnvironment, SalakDescContext};
-use crate::{FromEnvironment, PropertyError, Res, SalakContext};
+use crate::{FromEnvironment, IsProperty, Property, PropertyError, Res, SalakContext};
 use std::{
+    borrow::Cow,
     collections::HashSet,
     ffi::OsString,
+    marker::PhantomData,
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
     path::PathBuf,
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
-/// Raw property, it is a temprory representation of property, which can be either [`&str`] or [`String`], or other values.
-#[derive(Clone, Debug)]
-pub enum Property<'a> {
-    /// [`&str`] holder.
-    S(&'a str),
-    /// [`String`] holder.
-    O(String),
-    /// Number holder.
-    I(i64),
-    /// Float holder.
-    F(f64),
-    /// Bool holder.
-    B(bool),
-}
-
-/// Any object implements this trait is automatically implmenting [`crate::FromEnvironment`].
-///
-/// This trait defines how to parse value from property, and defines specific behaviors such as
-/// how empty string being parsed.
-pub trait IsProperty: Sized {
-    /// Check if empty string means property does not exist.
-    /// In most case this is true, except String.
-    #[inline]
-    fn is_empty(p: &Property<'_>) -> bool {
-        match p {
-            Property::S(s) => s.is_empty(),
-            Property::O(s) => s.is_empty(),
-            _ => false,
-        }
-    }
-
-    /// Parse value from property.
-    fn from_property(_: Property<'_>) -> Res<Self>;
-}
-
 impl<T: IsProperty> FromEnvironment for T {
     #[inline]
     fn from_env(val: Option<Property<'_>>, env: &mut SalakContext<'_>) -> Res<Self> {
@@ -61,8 +28,7 @@ impl<T: IsProperty> FromEnvironment for T {
 impl<T: IsProperty> DescFromEnvironment for T {
     #[inline]
     fn key_desc(env: &mut SalakDescContext<'_>) {
-        env.current.ignore = false;
-        env.current.set_required(true);
+        env.leaf_desc();
     }
 }
 
@@ -85,94 +51,6 @@ impl PrefixedFromEnvironment for () {
     }
 }
 
-#[inline]
-fn check_f64(f: f64) -> Result<f64, PropertyError> {
-    if f.is_finite() {
-        Ok(f)
-    } else {
-        Err(PropertyError::parse_fail("f64 value is infinite"))
-    }
-}
-
-impl IsProperty for String {
-    #[inline]
-    fn is_empty(_: &Property<'_>) -> bool {
-        false
-    }
-    #[inline]
-    fn from_property(p: Property<'_>) -> Res<Self> {
-        Ok(match p {
-            Property::S(v) => v.to_string(),
-            Property::O(v) => v,
-            Property::I(v) => v.to_string(),
-            Property::F(v) => check_f64(v)?.to_string(),
-            Property::B(v) => v.to_string(),
-        })
-    }
-}
-impl IsProperty for bool {
-    #[inline]
-    fn from_property(p: Property<'_>) -> Res<Self> {
-        fn str_to_bool(v: &str) -> Res<bool> {
-            match v {
-                "yes" | "true" => Ok(true),
-                "no" | "false" => Ok(false),
-                _ => Err(PropertyError::parse_fail("invalid bool value")),
-            }
-        }
-        match p {
-            Property::B(v) => Ok(v),
-            Property::S(v) => str_to_bool(v),
-            Property::O(v) => str_to_bool(&v),
-            _ => Err(PropertyError::parse_fail("can not num to bool")),
-        }
-    }
-}
-
-macro_rules! impl_property_num {
-    ($($x:ident),+) => {$(
-            impl IsProperty for $x {
-                #[inline]
-                fn from_property(p: Property<'_>) -> Res<Self> {
-                    use std::convert::TryFrom;
-                    Ok(match p {
-                    Property::S(s) => s.parse::<$x>()?,
-                    Property::O(s) => s.parse::<$x>()?,
-                    Property::I(s) => $x::try_from(s)?,
-                    Property::F(s) => check_f64(s)? as $x,
-                    _ => return Err(PropertyError::parse_fail("can not convert bool to num")),
-                    })
-                }
-
-            }
-
-            )+}
-}
-
-impl_property_num!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, isize, usize);
-
-macro_rules! impl_property_float {
-    ($($x:ident),+) => {$(
-            #[allow(trivial_numeric_casts)]
-            impl IsProperty for $x {
-                #[inline]
-                fn from_property(p: Property<'_>) -> Res<Self> {
-                    Ok(match p {
-                    Property::S(s) => s.parse::<$x>()?,
-                    Property::O(s) => s.parse::<$x>()?,
-                    Property::I(s) => s as $x,
-                    Property::F(s) => check_f64(s)? as $x,
-                    _ => return Err(PropertyError::parse_fail("can not convert bool to num")),
-                    })
-                }
-
-            }
-
-            )+}
-}
-
-impl_property_float!(f32, f64);
-
 #[inline]
 fn parse_duration_from_str(du: &str) -> Res<Duration> {
     let mut i = 0;
@@ -216,15 +94,74 @@ impl IsProperty for Duration {
         match p {
             Property::O(du) => parse_duration_from_str(&du),
             Property::S(du) => parse_duration_from_str(du),
-            Property::I(seconds) => Ok(Duration::from_secs(seconds as u64)),
+            Property::I(seconds) => {
+                use std::convert::TryFrom;
+                Ok(Duration::from_secs(u64::try_from(seconds)?))
+            }
             Property::F(sec) => Ok(Duration::new(0, 0).mul_f64(sec)),
             Property::B(_) => Err(PropertyError::parse_fail("bool cannot convert to duration")),
         }
     }
 }
 
+/// Time of `secs` seconds and `nanos` nanoseconds after unix epoch, `secs` can be negative.
+fn from_epoch(secs: i64, nanos: u32) -> Res<SystemTime> {
+    let time = if secs >= 0 {
+        UNIX_EPOCH.checked_add(Duration::new(secs as u64, nanos))
+    } else {
+        UNIX_EPOCH
+            .checked_sub(Duration::from_secs(secs.unsigned_abs()))
+            .and_then(|t| t.checked_add(Duration::from_nanos(nanos.into())))
+    };
+    time.ok_or_else(|| PropertyError::parse_fail("time out of range"))
+}
+
+/// Parse RFC3339 time, such as `2021-06-01T12:00:00Z` or `2021-06-01 12:00:00.5+08:00`.
+#[cfg(feature = "chrono")]
+fn parse_rfc3339(v: &str) -> Res<SystemTime> {
+    Ok(chrono::DateTime::parse_from_rfc3339(v)?.into())
+}
+
+#[cfg(not(feature = "chrono"))]
+fn parse_rfc3339(_: &str) -> Res<SystemTime> {
+    Err(PropertyError::parse_fail(
+        "rfc3339 time requires feature chrono",
+    ))
+}
+
+/// Parse [`SystemTime`] from unix epoch seconds, or RFC3339 time by feature 'chrono'.
+impl IsProperty for SystemTime {
+    fn from_property(p: Property<'_>) -> Res<Self> {
+        fn parse(v: &str) -> Res<SystemTime> {
+            match v.parse::<i64>() {
+                Ok(secs) => from_epoch(secs, 0),
+                _ => parse_rfc3339(v.trim()),
+            }
+        }
+        match p {
+            Property::O(v) => parse(&v),
+            Property::S(v) => parse(v),
+            Property::I(secs) => {
+                use std::convert::TryFrom;
+                from_epoch(i64::try_from(secs)?, 0)
+            }
+            Property::F(secs) => {
+                let du = Duration::try_from_secs_f64(secs.abs())
+                    .map_err(|_| PropertyError::parse_fail("invalid epoch seconds"))?;
+                if secs >= 0.0 {
+                    UNIX_EPOCH.checked_add(du)
+                } else {
+                    UNIX_EPOCH.checked_sub(du)
+                }
+                .ok_or_else(|| PropertyError::parse_fail("time out of range"))
+            }
+            Property::B(_) => Err(PropertyError::parse_fail("bool cannot convert to time")),
+        }
+    }
+}
+
 /// Sub key is partial [`Key`] having values with either `[a-z][_a-z0-9]*` or [`usize`].
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub(crate) enum SubKey<'a> {
     /// Str sub key.
     S(&'a str),
@@ -232,23 +169,63 @@ pub(crate) enum SubKey<'a> {
     I(usize),
 }
 
-impl SubKey<'_> {
+impl<'a> SubKey<'a> {
     pub(crate) fn is_empty(&self) -> bool {
         if let SubKey::S(v) = self {
             return v.is_empty();
         }
         false
     }
+
+    /// Split key path such as `a.b[0]` into sub keys.
+    pub(crate) fn parse_path(key: &'a str) -> Vec<SubKey<'a>> {
+        let mut v = vec![];
+        for n in key.split(&P[..]) {
+            if let Some(c) = n.chars().next() {
+                if c.is_ascii_digit() {
+                    if let Ok(i) = n.parse() {
+                        v.push(SubKey::I(i));
+                        continue;
+                    }
+                }
+                v.push(SubKey::S(n));
+            }
+        }
+        v
+    }
+}
+
+/// Segment of [`Key`], read only view of sub key, see [`Key::segments()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Segment<'a> {
+    /// Name segment, such as `b` in `a.b[2]`.
+    Name(&'a str),
+    /// Index segment, such as `2` in `a.b[2]`.
+    Index(usize),
+}
+
+impl<'a> From<SubKey<'a>> for Segment<'a> {
+    #[inline]
+    fn from(k: SubKey<'a>) -> Self {
+        match k {
+            SubKey::S(v) => Segment::Name(v),
+            SubKey::I(v) => Segment::Index(v),
+        }
+    }
 }
 
 lazy_static::lazy_static! {
     static ref P: &'static [char] = &['.', '[', ']'];
 }
 /// Key with a string buffer, can be avoid allocating memory when parsing configuration.
+///
+/// Sub keys are copied into the buffer, so keys can be pushed without borrowing them.
 #[derive(Debug)]
 pub struct Key<'a> {
     buf: String,
-    key: Vec<SubKey<'a>>,
+    /// Start of each sub key in `buf`, and index for index sub keys.
+    key: Vec<(usize, Option<usize>)>,
+    _data: PhantomData<&'a str>,
 }
 
 impl<'a> Key<'a> {
@@ -257,21 +234,21 @@ impl<'a> Key<'a> {
         Self {
             buf: String::new(),
             key: vec![],
+            _data: PhantomData,
         }
     }
 
     pub(crate) fn from_str(key: &'a str) -> Self {
-        let mut k = Self::new();
-        for n in key.split(&P[..]) {
-            if let Some(c) = n.chars().next() {
-                if c.is_ascii_digit() {
-                    if let Ok(v) = n.parse() {
-                        k.push(SubKey::I(v));
-                        continue;
-                    }
-                }
-                k.push(SubKey::S(n));
-            }
+        // Sub keys are parsed into the key directly, and the buffer is allocated once,
+        // so lookups of plain keys only allocate twice.
+        let path = SubKey::parse_path(key);
+        let mut k = Self {
+            buf: String::with_capacity(key.len() + 1),
+            key: Vec::with_capacity(path.len()),
+            _data: PhantomData,
+        };
+        for n in path {
+            k.push(n);
         }
         k
     }
@@ -281,9 +258,24 @@ impl<'a> Key<'a> {
         self.as_str().replace("[0]", "[*]")
     }
 
+    fn sub_key(&self, i: usize) -> SubKey<'_> {
+        match self.key[i] {
+            (_, Some(v)) => SubKey::I(v),
+            (start, _) => {
+                let end = self.key.get(i + 1).map_or(self.buf.len(), |k| k.0);
+                SubKey::S(&self.buf[start + 1..end])
+            }
+        }
+    }
+
     #[allow(dead_code)]
-    pub(crate) fn iter(&self) -> std::slice::Iter<'_, SubKey<'_>> {
-        self.key.iter()
+    pub(crate) fn iter(&self) -> impl Iterator<Item = SubKey<'_>> {
+        (0..self.key.len()).map(move |i| self.sub_key(i))
+    }
+
+    /// Iterate segments of key, such as `a`, `b`, `2`, `c` for `a.b[2].c`.
+    pub fn segments(&self) -> impl Iterator<Item = Segment<'_>> {
+        self.iter().map(Segment::from)
     }
 
     pub(crate) fn as_str(&self) -> &str {
@@ -293,25 +285,25 @@ impl<'a> Key<'a> {
         self.buf.as_str()
     }
 
-    pub(crate) fn push(&mut self, k: SubKey<'a>) {
-        match &k {
+    pub(crate) fn push(&mut self, k: SubKey<'_>) {
+        let start = self.buf.len();
+        match k {
             SubKey::S(v) => {
                 self.buf.push('.');
-                self.buf.push_str(*v);
+                self.buf.push_str(v);
+                self.key.push((start, None));
             }
             SubKey::I(v) => {
-                self.buf.push_str(&format!("[{}]", *v));
+                use std::fmt::Write;
+                let _ = write!(self.buf, "[{}]", v);
+                self.key.push((start, Some(v)));
             }
         }
-        self.key.push(k);
     }
 
     pub(crate) fn pop(&mut self) {
-        if let Some(v) = self.key.pop() {
-            match v {
-                SubKey::S(n) => self.buf.truncate(self.buf.len() - n.len() - 1),
-                SubKey::I(n) => self.buf.truncate(self.buf.len() - n.to_string().len() - 2),
-            }
+        if let Some((start, _)) = self.key.pop() {
+            self.buf.truncate(start);
         }
     }
 }
@@ -343,7 +335,7 @@ impl From<usize> for SubKey<'_> {
 /// Sub key collection, which stands for lists of sub keys with same prefix.
 #[derive(Debug)]
 pub struct SubKeys<'a> {
-    keys: HashSet<&'a str>,
+    keys: HashSet<Cow<'a, str>>,
     upper: Option<usize>,
 }
 
@@ -352,30 +344,45 @@ impl<'a> SubKeys<'a> {
     pub(crate) fn insert<K: Into<SubKey<'a>>>(&mut self, key: K) {
         match key.into() {
             SubKey::S(s) => {
-                self.keys.insert(s);
+                self.keys.insert(Cow::Borrowed(s));
             }
-            SubKey::I(i) => {
-                if let Some(max) = self.upper {
-                    if i <= max {
-                        return;
-                    }
+            SubKey::I(i) => self.insert_index(i),
+        }
+    }
+
+    /// Insert a sub key owned by the caller, such as keys of sources behind locks.
+    pub(crate) fn insert_owned(&mut self, key: &str) {
+        match SubKey::from(key) {
+            SubKey::S(s) => {
+                if !self.keys.contains(s) {
+                    self.keys.insert(Cow::Owned(s.to_string()));
                 }
-                self.upper = Some(i);
+            }
+            SubKey::I(i) => self.insert_index(i),
+        }
+    }
+
+    fn insert_index(&mut self, i: usize) {
+        if let Some(max) = self.upper {
+            if i <= max {
+                return;
             }
         }
+        self.upper = Some(i);
     }
 
-    pub(crate) fn str_keys(&self) -> Vec<&'a str> {
+    /// Get string sub keys.
+    pub fn str_keys(&self) -> Vec<&str> {
         self.keys
             .iter()
+            .map(|a| a.as_ref())
             .filter(|a| {
                 if let Some(c) = a.chars().next() {
-                    c < '0' && c > '9'
+                    !c.is_ascii_digit()
                 } else {
                     false
                 }
             })
-            .copied()
             .collect()
     }
 
@@ -387,10 +394,17 @@ impl<'a> SubKeys<'a> {
         }
     }
 
+    /// Get max index of sub keys.
     #[inline]
-    pub(crate) fn max(&self) -> Option<usize> {
+    pub fn max(&self) -> Option<usize> {
         self.upper
     }
+
+    #[inline]
+    #[allow(dead_code)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.keys.is_empty() && self.upper.is_none()
+    }
 }
 
 macro_rules! impl_property_from_str {
@@ -431,6 +445,7 @@ mod ipnet {
 #[cfg(test)]
 mod tests {
     use crate::*;
+    use std::borrow::Cow;
 
     #[test]
     fn property_test() {
@@ -473,55 +488,55 @@ mod tests {
         validate::<bool>(
             &env,
             "a",
-            "Err(ParseFail(None, SalakParseError(\"invalid bool value\")))",
+            "Err(ParseFail(Some(\"a\"), SalakParseError(\"invalid bool value\")))",
         );
         validate::<bool>(&env, "b", "Err(RecursiveFail(\"b\"))");
         validate::<bool>(
             &env,
             "c",
-            "Err(ParseFail(None, SalakParseError(\"invalid bool value\")))",
+            "Err(ParseFail(Some(\"c\"), SalakParseError(\"invalid bool value\")))",
         );
         validate::<bool>(&env, "d", "Err(ResolveNotFound(\"z\"))");
         validate::<bool>(&env, "e", "Err(NotFound(\"e\"))");
         validate::<bool>(
             &env,
             "f",
-            "Err(ParseFail(None, SalakParseError(\"invalid bool value\")))",
+            "Err(ParseFail(Some(\"f\"), SalakParseError(\"invalid bool value\")))",
         );
         validate::<bool>(
             &env,
             "g",
-            "Err(ParseFail(None, SalakParseError(\"invalid bool value\")))",
+            "Err(ParseFail(Some(\"g\"), SalakParseError(\"invalid bool value\")))",
         );
         validate::<bool>(
             &env,
             "h",
-            "Err(ParseFail(None, SalakParseError(\"invalid bool value\")))",
+            "Err(ParseFail(Some(\"h\"), SalakParseError(\"invalid bool value\")))",
         );
         validate::<bool>(
             &env,
             "i",
-            "Err(ParseFail(None, SalakParseError(\"invalid bool value\")))",
+            "Err(ParseFail(Some(\"i\"), SalakParseError(\"invalid bool value\")))",
         );
         validate::<bool>(
             &env,
             "j",
-            "Err(ParseFail(None, SalakParseError(\"invalid bool value\")))",
+            "Err(ParseFail(Some(\"j\"), SalakParseError(\"invalid bool value\")))",
         );
         validate::<bool>(
             &env,
             "k",
-            "Err(ParseFail(None, SalakParseError(\"invalid bool value\")))",
+            "Err(ParseFail(Some(\"k\"), SalakParseError(\"invalid bool value\")))",
         );
         validate::<bool>(
             &env,
             "l",
-            "Err(ParseFail(None, SalakParseError(\"invalid bool value\")))",
+            "Err(ParseFail(Some(\"l\"), SalakParseError(\"invalid bool value\")))",
         );
         validate::<bool>(
             &env,
             "m",
-            "Err(ParseFail(None, SalakParseError(\"invalid bool value\")))",
+            "Err(ParseFail(Some(\"m\"), SalakParseError(\"invalid bool value\")))",
         );
 
         validate::<u8>(&env, "a", "Ok(0)");
@@ -533,25 +548,25 @@ mod tests {
         validate::<u8>(
             &env,
             "g",
-            "Err(ParseFail(None, ParseIntError { kind: InvalidDigit }))",
+            "Err(ParseFail(Some(\"g\"), ParseIntError { kind: InvalidDigit }))",
         );
         validate::<u8>(&env, "h", "Ok(0)");
         validate::<u8>(
             &env,
             "i",
-            "Err(ParseFail(None, ParseIntError { kind: InvalidDigit }))",
+            "Err(ParseFail(Some(\"i\"), ParseIntError { kind: InvalidDigit }))",
         );
         validate::<u8>(&env, "j", "Ok(0)");
         validate::<u8>(
             &env,
             "k",
-            "Err(ParseFail(None, ParseIntError { kind: InvalidDigit }))",
+            "Err(ParseFail(Some(\"k\"), ParseIntError { kind: InvalidDigit }))",
         );
         validate::<u8>(&env, "l", "Ok(0)");
         validate::<u8>(
             &env,
             "m",
-            "Err(ParseFail(None, ParseIntError { kind: InvalidDigit }))",
+            "Err(ParseFail(Some(\"m\"), ParseIntError { kind: InvalidDigit }))",
         );
 
         validate::<Option<u8>>(&env, "a", "Ok(Some(0))");
@@ -563,26 +578,193 @@ mod tests {
         validate::<Option<u8>>(
             &env,
             "g",
-            "Err(ParseFail(None, ParseIntError { kind: InvalidDigit }))",
+            "Err(ParseFail(Some(\"g\"), ParseIntError { kind: InvalidDigit }))",
         );
         validate::<Option<u8>>(&env, "h", "Ok(Some(0))");
         validate::<Option<u8>>(
             &env,
             "i",
-            "Err(ParseFail(None, ParseIntError { kind: InvalidDigit }))",
+            "Err(ParseFail(Some(\"i\"), ParseIntError { kind: InvalidDigit }))",
         );
         validate::<Option<u8>>(&env, "j", "Ok(Some(0))");
         validate::<Option<u8>>(
             &env,
             "k",
-            "Err(ParseFail(None, ParseIntError { kind: InvalidDigit }))",
+            "Err(ParseFail(Some(\"k\"), ParseIntError { kind: InvalidDigit }))",
         );
         validate::<Option<u8>>(&env, "l", "Ok(Some(0))");
         validate::<Option<u8>>(
             &env,
             "m",
-            "Err(ParseFail(None, ParseIntError { kind: InvalidDigit }))",
+            "Err(ParseFail(Some(\"m\"), ParseIntError { kind: InvalidDigit }))",
+        );
+    }
+
+    #[test]
+    fn cow_test() {
+        let env = Salak::builder()
+            .set("name", "salak")
+            .set("empty", "")
+            .build()
+            .unwrap();
+        let name = env.require::<Cow<'static, str>>("name").unwrap();
+        assert!(matches!(name, Cow::Owned(_)));
+        assert_eq!("salak", name);
+        assert_eq!(
+            Some(Cow::Borrowed("")),
+            env.require::<Option<Cow<'static, str>>>("empty").unwrap()
+        );
+        assert_eq!(
+            None,
+            env.require::<Option<Cow<'static, str>>>("none").unwrap()
         );
+        let def: Cow<'static, str> = env
+            .require::<Option<Cow<'static, str>>>("none")
+            .unwrap()
+            .unwrap_or(Cow::Borrowed("default"));
+        assert!(matches!(def, Cow::Borrowed("default")));
+    }
+
+    #[test]
+    fn require_duration_test() {
+        use std::time::Duration;
+        let env = Salak::builder()
+            .set("timeout", "3s")
+            .set("invalid", "3x")
+            .build()
+            .unwrap();
+        assert_eq!(
+            Duration::from_secs(3),
+            env.require_duration("timeout", "5s").unwrap()
+        );
+        assert_eq!(
+            Duration::from_secs(3),
+            env.require_duration("timeout", "invalid").unwrap()
+        );
+        assert_eq!(
+            Duration::from_millis(100),
+            env.require_duration("none", "100ms").unwrap()
+        );
+        assert_eq!(
+            "Err(ParseFail(Some(\"none\"), SalakParseError(\"Invalid duration\")))",
+            format!("{:?}", env.require_duration("none", "5x"))
+        );
+        assert!(env.require_duration("invalid", "5s").is_err());
+    }
+
+    #[test]
+    fn require_into_test() {
+        #[derive(Debug, PartialEq)]
+        struct MyPort(u16);
+
+        impl From<u16> for MyPort {
+            fn from(v: u16) -> Self {
+                MyPort(v)
+            }
+        }
+
+        let env = Salak::builder().set("port", "8080").build().unwrap();
+        assert_eq!(
+            MyPort(8080),
+            env.require_into::<u16, MyPort>("port").unwrap()
+        );
+        let port: Option<MyPort> = env.require_into::<u16, _>("port").ok();
+        assert_eq!(Some(MyPort(8080)), port);
+        assert!(matches!(
+            env.require_into::<u16, MyPort>("none"),
+            Err(PropertyError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn require_with_test() {
+        let env = Salak::builder()
+            .set("name", "  Hello ")
+            .set("port", "0")
+            .build()
+            .unwrap();
+        assert_eq!(
+            "hello",
+            env.require_with::<String, _>("name", |v| Ok(v.trim().to_lowercase()))
+                .unwrap()
+        );
+        assert_eq!(
+            "Err(ParseFail(Some(\"port\"), SalakParseError(\"port must be positive\")))",
+            format!(
+                "{:?}",
+                env.require_with::<u16, u16>("port", |v| match v {
+                    0 => Err(PropertyError::parse_fail("port must be positive")),
+                    v => Ok(v),
+                })
+            )
+        );
+        assert_eq!(
+            "Err(NotFound(\"none\"))",
+            format!("{:?}", env.require_with::<u16, u16>("none", Ok))
+        );
+    }
+
+    #[test]
+    fn require_vec_csv_test() {
+        let env = Salak::builder()
+            .set("hosts", "a, b,c")
+            .set("ports", "80;443")
+            .set("empty", "")
+            .set("bad", "80;x")
+            .set("gap", "a,,b")
+            .build()
+            .unwrap();
+        assert_eq!(
+            vec!["a", "b", "c"],
+            env.require_vec_csv::<String>("hosts", ',').unwrap()
+        );
+        assert_eq!(
+            vec![80u16, 443],
+            env.require_vec_csv::<u16>("ports", ';').unwrap()
+        );
+        assert!(env.require_vec_csv::<u16>("empty", ',').unwrap().is_empty());
+        assert!(matches!(
+            env.require_vec_csv::<u16>("bad", ';'),
+            Err(PropertyError::ParseFail(Some(key), _)) if key == "bad"
+        ));
+        assert!(env.require_vec_csv::<u16>("ports", ',').is_err());
+        assert!(matches!(
+            env.require_vec_csv::<u16>("none", ','),
+            Err(PropertyError::NotFound(_))
+        ));
+        assert_eq!(
+            vec![Some("a".to_string()), None, Some("b".to_string())],
+            env.require_vec_csv::<Option<String>>("gap", ',').unwrap()
+        );
+        assert!(matches!(
+            env.require_vec_csv::<String>("gap", ','),
+            Err(PropertyError::NotFound(key)) if key == "gap[1]"
+        ));
+    }
+
+    #[test]
+    fn set_typed_test() {
+        let env = Salak::builder()
+            .set("str_bool", "1")
+            .set_typed("typed_bool", 1)
+            .set_typed("flag", true)
+            .set_typed("ratio", 0.5)
+            .set_typed("big", u64::MAX)
+            .build()
+            .unwrap();
+        assert_eq!(
+            "Err(ParseFail(Some(\"str_bool\"), SalakParseError(\"invalid bool value\")))",
+            format!("{:?}", env.require::<bool>("str_bool"))
+        );
+        assert_eq!(
+            "Err(ParseFail(Some(\"typed_bool\"), SalakParseError(\"can not num to bool\")))",
+            format!("{:?}", env.require::<bool>("typed_bool"))
+        );
+        assert_eq!(1, env.require::<u8>("typed_bool").unwrap());
+        assert!(env.require::<bool>("flag").unwrap());
+        assert_eq!(0.5, env.require::<f64>("ratio").unwrap());
+        assert_eq!(u64::MAX, env.require::<u64>("big").unwrap());
+        assert_eq!("true", env.require::<String>("flag").unwrap());
     }
 
     #[test]
@@ -603,30 +785,53 @@ mod tests {
         assert_eq!(true, bool::from_property(Property::F(1.0)).is_err());
     }
 
+    #[test]
+    fn float_tests() {
+        let env = Salak::builder()
+            .set("inf", "inf")
+            .set("neg_inf", "-infinity")
+            .set("nan", "NaN")
+            .set("big", "1e39")
+            .set("ratio", "1.5")
+            .build()
+            .unwrap();
+        for key in &["inf", "neg_inf", "nan"] {
+            assert!(env.require::<f64>(key).is_err());
+            assert!(env.require::<f32>(key).is_err());
+        }
+        assert_eq!(1e39, env.require::<f64>("big").unwrap());
+        assert!(env.require::<f32>("big").is_err());
+        assert_eq!(1.5, env.require::<f64>("ratio").unwrap());
+        assert_eq!(1.5, env.require::<f32>("ratio").unwrap());
+        assert!(f64::from_property(Property::S("inf")).is_err());
+        assert!(f64::from_property(Property::F(f64::NAN)).is_err());
+    }
+
     #[quickcheck]
     fn num_tests(i: i64) {
         assert_eq!(
             i,
             i64::from_property(Property::O(format!("{}", i))).unwrap()
         );
-        assert_eq!(i, i64::from_property(Property::I(i)).unwrap());
+        assert_eq!(i, i64::from_property(Property::I(i.into())).unwrap());
         assert_eq!(true, i64::from_property(Property::B(true)).is_err());
     }
 
     #[quickcheck]
     fn i64_convert_tests(i: i64) -> bool {
-        let u8: Result<u8, PropertyError> = IsProperty::from_property(Property::I(i));
-        let u16: Result<u16, PropertyError> = IsProperty::from_property(Property::I(i));
-        let u32: Result<u32, PropertyError> = IsProperty::from_property(Property::I(i));
-        let u64: Result<u64, PropertyError> = IsProperty::from_property(Property::I(i));
-        let u128: Result<u128, PropertyError> = IsProperty::from_property(Property::I(i));
-        let i8: Result<i8, PropertyError> = IsProperty::from_property(Property::I(i));
-        let i16: Result<i16, PropertyError> = IsProperty::from_property(Property::I(i));
-        let i32: Result<i32, PropertyError> = IsProperty::from_property(Property::I(i));
-        let i64: Result<i64, PropertyError> = IsProperty::from_property(Property::I(i));
-        let i128: Result<i128, PropertyError> = IsProperty::from_property(Property::I(i));
-        let f32: Result<f32, PropertyError> = IsProperty::from_property(Property::I(i));
-        let f64: Result<f64, PropertyError> = IsProperty::from_property(Property::I(i));
+        let p = i128::from(i);
+        let u8: Result<u8, PropertyError> = IsProperty::from_property(Property::I(p));
+        let u16: Result<u16, PropertyError> = IsProperty::from_property(Property::I(p));
+        let u32: Result<u32, PropertyError> = IsProperty::from_property(Property::I(p));
+        let u64: Result<u64, PropertyError> = IsProperty::from_property(Property::I(p));
+        let u128: Result<u128, PropertyError> = IsProperty::from_property(Property::I(p));
+        let i8: Result<i8, PropertyError> = IsProperty::from_property(Property::I(p));
+        let i16: Result<i16, PropertyError> = IsProperty::from_property(Property::I(p));
+        let i32: Result<i32, PropertyError> = IsProperty::from_property(Property::I(p));
+        let i64: Result<i64, PropertyError> = IsProperty::from_property(Property::I(p));
+        let i128: Result<i128, PropertyError> = IsProperty::from_property(Property::I(p));
+        let f32: Result<f32, PropertyError> = IsProperty::from_property(Property::I(p));
+        let f64: Result<f64, PropertyError> = IsProperty::from_property(Property::I(p));
         vec![
             i >= 0 && i <= (u8::MAX as i64) && u8.is_ok() || u8.is_err(),
             i >= 0 && i <= (u16::MAX as i64) && u16.is_ok() || u16.is_err(),
@@ -645,6 +850,60 @@ mod tests {
         .all(|a| *a)
     }
 
+    #[quickcheck]
+    fn u64_round_trip_tests(u: u64) {
+        assert_eq!(u, u64::from_property(Property::I(u.into())).unwrap());
+        assert_eq!(
+            u.to_string(),
+            String::from_property(Property::I(u.into())).unwrap()
+        );
+        assert_eq!(
+            u >= 1 << 63,
+            i64::from_property(Property::I(u.into())).is_err()
+        );
+    }
+
+    #[quickcheck]
+    fn i128_round_trip_tests(hi: i64, lo: u64) {
+        use std::convert::TryFrom;
+        let i = (i128::from(hi) << 64) | i128::from(lo);
+        assert_eq!(i, i128::from_property(Property::I(i)).unwrap());
+        assert_eq!(
+            i,
+            i128::from_property(Property::O(String::from_property(Property::I(i)).unwrap()))
+                .unwrap()
+        );
+        assert_eq!(
+            u64::try_from(i).is_ok(),
+            u64::from_property(Property::I(i)).is_ok()
+        );
+    }
+
+    #[test]
+    fn non_zero_test() {
+        use std::num::*;
+        let env = Salak::builder()
+            .set("zero", "0")
+            .set("one", "1")
+            .set("neg", "-1")
+            .build()
+            .unwrap();
+        assert_eq!(
+            "Err(ParseFail(Some(\"zero\"), SalakParseError(\"zero is not allowed\")))",
+            format!("{:?}", env.require::<NonZeroU32>("zero"))
+        );
+        assert_eq!(
+            NonZeroU32::new(1),
+            env.require::<Option<NonZeroU32>>("one").unwrap()
+        );
+        assert_eq!(
+            NonZeroI64::new(-1),
+            env.require::<Option<NonZeroI64>>("neg").unwrap()
+        );
+        assert!(env.require::<NonZeroU8>("neg").is_err());
+        assert_eq!(None, env.require::<Option<NonZeroUsize>>("none").unwrap());
+    }
+
     #[quickcheck]
     fn f64_convert_tests(i: f64) -> bool {
         let u8: Result<u8, PropertyError> = IsProperty::from_property(Property::F(i));
@@ -715,6 +974,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn char_test() {
+        let env = Salak::builder()
+            .set("sep", ",")
+            .set("unicode", "中")
+            .set("empty", "")
+            .set("multi", "ab")
+            .set_typed("digit", 7u8)
+            .build()
+            .unwrap();
+        assert_eq!(',', env.require::<char>("sep").unwrap());
+        assert_eq!('中', env.require::<char>("unicode").unwrap());
+        assert_eq!('7', env.require::<char>("digit").unwrap());
+        assert_eq!(None, env.require::<Option<char>>("empty").unwrap());
+        assert!(matches!(
+            env.require::<char>("empty"),
+            Err(PropertyError::NotFound(_))
+        ));
+        assert!(matches!(
+            env.require::<char>("multi"),
+            Err(PropertyError::ParseFail(_, _))
+        ));
+    }
+
+    #[test]
+    fn system_time_test() {
+        use std::time::{Duration, SystemTime, UNIX_EPOCH};
+        let env = Salak::builder()
+            .set("epoch", "1622548800")
+            .set_typed("epoch_i", 1622548800u32)
+            .set_typed("epoch_f", 1622548800.5f64)
+            .set_typed("before", -86400i64)
+            .set("utc", "2021-06-01T12:00:00Z")
+            .set("offset", "2021-06-01 20:00:00.5+08:00")
+            .set("leap", "2020-02-29T00:00:00z")
+            .set("bad_date", "2021-02-29T00:00:00Z")
+            .set("bad_zone", "2021-06-01T12:00:00")
+            .set("bad", "yesterday")
+            .build()
+            .unwrap();
+        let time = |k: &str| env.require::<SystemTime>(k);
+        let at = |secs: u64, nanos: u32| UNIX_EPOCH + Duration::new(secs, nanos);
+        assert_eq!(at(1622548800, 0), time("epoch").unwrap());
+        assert_eq!(at(1622548800, 0), time("epoch_i").unwrap());
+        assert_eq!(at(1622548800, 500_000_000), time("epoch_f").unwrap());
+        assert_eq!(
+            UNIX_EPOCH - Duration::from_secs(86400),
+            time("before").unwrap()
+        );
+        #[cfg(feature = "chrono")]
+        {
+            assert_eq!(at(1622548800, 0), time("utc").unwrap());
+            assert_eq!(at(1622548800, 500_000_000), time("offset").unwrap());
+            assert_eq!(at(1582934400, 0), time("leap").unwrap());
+        }
+        #[cfg(not(feature = "chrono"))]
+        for k in &["utc", "offset", "leap"] {
+            assert!(time(k).is_err());
+        }
+        for k in &["bad_date", "bad_zone", "bad"] {
+            assert!(time(k).is_err());
+        }
+    }
+
     #[derive(Debug)]
     struct Config {
         i8: i8,
@@ -775,6 +1098,96 @@ mod tests {
         assert_key("0", "[0]");
     }
 
+    #[test]
+    fn require_raw_test() {
+        let env = Salak::builder()
+            .set("x", "world")
+            .set("a", "hello ${x}")
+            .set("b", "\\${x\\}")
+            .set("c", "${x}")
+            .set_typed("d", 8u8)
+            .build()
+            .unwrap();
+        assert_eq!("hello world", env.require::<String>("a").unwrap());
+        assert_eq!("hello ${x}", env.require_raw::<String>("a").unwrap());
+        assert_eq!("${x}", env.require::<String>("b").unwrap());
+        assert_eq!("\\${x\\}", env.require_raw::<String>("b").unwrap());
+        assert_eq!(8, env.require_raw::<u8>("d").unwrap());
+        assert_eq!(
+            "Err(NotFound(\"missing\"))",
+            format!("{:?}", env.require_raw::<String>("missing"))
+        );
+        assert!(matches!(
+            env.require_raw::<u8>("c"),
+            Err(PropertyError::ParseFail(Some(_), _))
+        ));
+    }
+
+    #[test]
+    fn placeholder_default_with_colon_test() {
+        let env = Salak::builder()
+            .set("host", "db")
+            .set("a", "${missing:postgres://localhost:5432/db}")
+            .set("b", "${missing:postgres://${host}:5432/db}")
+            .set("c", "${host:postgres://localhost:5432/db}")
+            .set("d", "${missing:a:b:}")
+            .build()
+            .unwrap();
+        assert_eq!(
+            "postgres://localhost:5432/db",
+            env.require::<String>("a").unwrap()
+        );
+        assert_eq!("postgres://db:5432/db", env.require::<String>("b").unwrap());
+        assert_eq!("db", env.require::<String>("c").unwrap());
+        assert_eq!("a:b:", env.require::<String>("d").unwrap());
+    }
+
+    #[test]
+    fn placeholder_depth_test() {
+        let mut builder = Salak::builder()
+            .set("x", "${y}")
+            .set("y", "${x}")
+            .set("k40", "end");
+        for i in 0..40 {
+            builder = builder.set(format!("k{}", i), format!("${{k{}}}", i + 1));
+        }
+        let env = builder.build().unwrap();
+        assert_eq!("end", env.require::<String>("k10").unwrap());
+        assert!(matches!(
+            env.require::<String>("k0"),
+            Err(PropertyError::RecursiveFail(_))
+        ));
+        assert!(matches!(
+            env.require::<String>("x"),
+            Err(PropertyError::RecursiveFail(_))
+        ));
+
+        let mut builder = Salak::builder()
+            .set("k40", "end")
+            .configure_max_placeholder_depth(40);
+        for i in 0..40 {
+            builder = builder.set(format!("k{}", i), format!("${{k{}}}", i + 1));
+        }
+        let env = builder.build().unwrap();
+        assert_eq!("end", env.require::<String>("k0").unwrap());
+    }
+
+    #[test]
+    fn segments_test() {
+        use crate::source::Segment;
+        let key = Key::from_str("a.b[2].c");
+        assert_eq!(
+            vec![
+                Segment::Name("a"),
+                Segment::Name("b"),
+                Segment::Index(2),
+                Segment::Name("c")
+            ],
+            key.segments().collect::<Vec<_>>()
+        );
+        assert_eq!(0, Key::from_str("").segments().count());
+    }
+
     #[test]
     fn key_modification_test() {
         fn assert_key<'a>(key: &mut Key<'a>, target: &'a str) {
@@ -796,5 +1209,13 @@ mod tests {
         assert_keys("redis", vec!["port", "host", "ssl", "pool"]);
         assert_keys("hello.hey", vec!["world"]);
         assert_keys("hello[0].hey", vec!["world"]);
+
+        let mut key = Key::from_str("a[10]");
+        key.push(SubKey::I(123));
+        assert_eq!("a[10][123]", key.as_str());
+        key.pop();
+        assert_eq!("a[10]", key.as_str());
+        key.pop();
+        assert_eq!("a", key.as_str());
     }
 }
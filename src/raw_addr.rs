@@ -0,0 +1,117 @@
+use std::{
+    net::{IpAddr, SocketAddr, ToSocketAddrs},
+    ops::Deref,
+    str::FromStr,
+};
+
+use crate::{IsProperty, Property, PropertyError, Res};
+
+/// A wrapper of [`SocketAddr`], parsing `host` or `host:port`, and use `P` as port
+/// if port is missing. Host name will be resolved when parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddrWithDefaultPort<const P: u16>(SocketAddr);
+
+impl<const P: u16> AddrWithDefaultPort<P> {
+    /// Get [`SocketAddr`].
+    #[inline]
+    pub fn into_inner(self) -> SocketAddr {
+        self.0
+    }
+
+    fn parse(addr: &str) -> Res<Self> {
+        let addr = addr.trim();
+        if let Ok(v) = SocketAddr::from_str(addr) {
+            return Ok(Self(v));
+        }
+        if let Ok(v) = IpAddr::from_str(addr.trim_start_matches('[').trim_end_matches(']')) {
+            return Ok(Self(SocketAddr::new(v, P)));
+        }
+        let (host, port) = match addr.rfind(':') {
+            Some(i) => (
+                &addr[..i],
+                addr[i + 1..]
+                    .parse::<u16>()
+                    .map_err(|_| PropertyError::parse_fail("invalid port"))?,
+            ),
+            None => (addr, P),
+        };
+        if host.is_empty() || host.contains(|c: char| c.is_whitespace() || c == '/') {
+            return Err(PropertyError::parse_fail("invalid host"));
+        }
+        match (host, port)
+            .to_socket_addrs()
+            .ok()
+            .and_then(|mut v| v.next())
+        {
+            Some(v) => Ok(Self(v)),
+            None => Err(PropertyError::parse_fail("can not resolve host")),
+        }
+    }
+}
+
+impl<const P: u16> Deref for AddrWithDefaultPort<P> {
+    type Target = SocketAddr;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const P: u16> IsProperty for AddrWithDefaultPort<P> {
+    #[inline]
+    fn from_property(p: Property<'_>) -> Res<Self> {
+        match p {
+            Property::S(v) => Self::parse(v),
+            Property::O(v) => Self::parse(&v),
+            _ => Err(PropertyError::parse_fail("can not convert to address")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{wrapper::AddrWithDefaultPort, *};
+    use std::net::SocketAddr;
+
+    type Redis = AddrWithDefaultPort<6379>;
+
+    #[test]
+    fn addr_test() {
+        let env = Salak::builder()
+            .set("a", "192.168.0.1")
+            .set("b", "192.168.0.1:6380")
+            .set("c", "::1")
+            .set("d", "[::1]:6380")
+            .set("e", "localhost")
+            .set("f", "192.168.0.1:x")
+            .set("g", "192.168.0.1:65536")
+            .set("h", ":6379")
+            .set("i", "local host")
+            .set("cluster[0]", "127.0.0.1")
+            .set("cluster[1]", "127.0.0.1:6380")
+            .build()
+            .unwrap();
+        let addr = |k: &str| env.require::<Redis>(k).map(|v| v.into_inner());
+        assert_eq!(
+            "192.168.0.1:6379".parse::<SocketAddr>().ok(),
+            addr("a").ok()
+        );
+        assert_eq!(
+            "192.168.0.1:6380".parse::<SocketAddr>().ok(),
+            addr("b").ok()
+        );
+        assert_eq!("[::1]:6379".parse::<SocketAddr>().ok(), addr("c").ok());
+        assert_eq!("[::1]:6380".parse::<SocketAddr>().ok(), addr("d").ok());
+        assert_eq!(6379, addr("e").unwrap().port());
+        assert!(addr("e").unwrap().ip().is_loopback());
+        for k in &["f", "g", "h", "i"] {
+            assert!(addr(k).is_err());
+        }
+        let cluster = env.require::<Vec<Redis>>("cluster").unwrap();
+        assert_eq!(
+            vec![6379, 6380],
+            cluster.iter().map(|v| v.port()).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use std::{net::IpAddr, ops::Deref, str::FromStr};
+
+use ipnet::IpNet;
+
+use crate::{IsProperty, Property, PropertyError, Res};
+
+/// A wrapper of [`IpNet`] for allowlists, parsing CIDRs such as `10.0.0.0/8` or `fd00::/8`.
+/// Single addresses such as `10.0.0.1` are parsed as networks with full prefix length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Cidr(IpNet);
+
+impl Cidr {
+    /// Get network.
+    #[inline]
+    pub fn into_inner(self) -> IpNet {
+        self.0
+    }
+
+    /// Check whether `addr` is in the network.
+    #[inline]
+    pub fn contains(&self, addr: &IpAddr) -> bool {
+        self.0.contains(addr)
+    }
+
+    fn parse(v: &str) -> Res<Self> {
+        let v = v.trim();
+        IpNet::from_str(v)
+            .or_else(|_| IpAddr::from_str(v).map(IpNet::from))
+            .map(Self)
+            .map_err(|_| PropertyError::parse_fail(&format!("invalid cidr {}", v)))
+    }
+}
+
+impl Deref for Cidr {
+    type Target = IpNet;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl IsProperty for Cidr {
+    #[inline]
+    fn from_property(p: Property<'_>) -> Res<Self> {
+        match p {
+            Property::S(v) => Self::parse(v),
+            Property::O(v) => Self::parse(&v),
+            _ => Err(PropertyError::parse_fail("can not convert to cidr")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{wrapper::Cidr, *};
+
+    #[test]
+    fn cidr_test() {
+        let env = Salak::builder()
+            .set("v4", "10.0.0.0/8")
+            .set("v6", "fd00::/8")
+            .set("host", "192.168.1.1")
+            .set("allow[0]", "127.0.0.0/8")
+            .set("allow[1]", "::1/128")
+            .set("bad_mask", "10.0.0.0/33")
+            .set("bad_addr", "10.0.0/8")
+            .build()
+            .unwrap();
+        let v4 = env.require::<Cidr>("v4").unwrap();
+        assert_eq!("10.0.0.0/8", v4.to_string());
+        assert!(v4.contains(&"10.1.2.3".parse().unwrap()));
+        assert!(!v4.contains(&"11.0.0.1".parse().unwrap()));
+        let v6 = env.require::<Cidr>("v6").unwrap();
+        assert_eq!(8, v6.prefix_len());
+        assert!(v6.contains(&"fd12::1".parse().unwrap()));
+        assert_eq!(
+            "192.168.1.1/32",
+            env.require::<Cidr>("host").unwrap().to_string()
+        );
+
+        let allow = env.require::<Vec<Cidr>>("allow").unwrap();
+        assert_eq!(2, allow.len());
+        assert!(allow.iter().any(|c| c.contains(&"::1".parse().unwrap())));
+
+        for k in &["bad_mask", "bad_addr"] {
+            assert!(matches!(
+                env.require::<Cidr>(k),
+                Err(PropertyError::ParseFail(_, _))
+            ));
+        }
+    }
+}
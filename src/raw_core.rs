@@ -0,0 +1,241 @@
+#[cfg(all(feature = "core-parse", not(feature = "std")))]
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+};
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+#[cfg(any(feature = "std", not(feature = "core-parse")))]
+use std::borrow::Cow;
+
+use crate::{PropertyError, Res};
+
+/// Raw property, it is a temprory representation of property, which can be either [`&str`] or [`String`], or other values.
+///
+/// Explicit empty strings, such as yaml `key: ""`, differ from missing keys, so `Option<String>`
+/// returns `Some("")` for them, while other types treat them as not found, see [`IsProperty::is_empty`].
+/// Yaml and json `null` are missing, so defaults and lower priority sources still apply.
+#[derive(Clone, Debug)]
+pub enum Property<'a> {
+    /// [`&str`] holder.
+    S(&'a str),
+    /// [`String`] holder.
+    O(String),
+    /// Number holder, wide enough to carry full-range [`u64`] and [`i128`] values.
+    I(i128),
+    /// Float holder.
+    F(f64),
+    /// Bool holder.
+    B(bool),
+}
+
+macro_rules! impl_into_property {
+    ($($x:ty => $y:ident),+) => {$(
+        impl From<$x> for Property<'_> {
+            #[inline]
+            fn from(v: $x) -> Self {
+                Property::$y(v.into())
+            }
+        }
+    )+};
+}
+
+impl_into_property!(
+    String => O,
+    bool => B,
+    i8 => I,
+    i16 => I,
+    i32 => I,
+    i64 => I,
+    i128 => I,
+    u8 => I,
+    u16 => I,
+    u32 => I,
+    u64 => I,
+    f32 => F,
+    f64 => F
+);
+
+impl<'a> From<&'a str> for Property<'a> {
+    #[inline]
+    fn from(v: &'a str) -> Self {
+        Property::S(v)
+    }
+}
+
+/// Any object implements this trait is automatically implmenting [`crate::FromEnvironment`].
+///
+/// This trait defines how to parse value from property, and defines specific behaviors such as
+/// how empty string being parsed.
+pub trait IsProperty: Sized {
+    /// Check if empty string means property does not exist.
+    /// In most case this is true, except String.
+    #[inline]
+    fn is_empty(p: &Property<'_>) -> bool {
+        match p {
+            Property::S(s) => s.is_empty(),
+            Property::O(s) => s.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Parse value from property.
+    fn from_property(_: Property<'_>) -> Res<Self>;
+}
+
+#[inline]
+fn check_f64(f: f64) -> Result<f64, PropertyError> {
+    if f.is_finite() {
+        Ok(f)
+    } else {
+        Err(PropertyError::parse_fail("float value is not finite"))
+    }
+}
+
+impl IsProperty for String {
+    #[inline]
+    fn is_empty(_: &Property<'_>) -> bool {
+        false
+    }
+    #[inline]
+    fn from_property(p: Property<'_>) -> Res<Self> {
+        Ok(match p {
+            Property::S(v) => v.to_string(),
+            Property::O(v) => v,
+            Property::I(v) => v.to_string(),
+            Property::F(v) => check_f64(v)?.to_string(),
+            Property::B(v) => v.to_string(),
+        })
+    }
+}
+
+impl IsProperty for Cow<'static, str> {
+    #[inline]
+    fn is_empty(_: &Property<'_>) -> bool {
+        false
+    }
+    #[inline]
+    fn from_property(p: Property<'_>) -> Res<Self> {
+        Ok(Cow::Owned(String::from_property(p)?))
+    }
+}
+
+impl IsProperty for bool {
+    #[inline]
+    fn from_property(p: Property<'_>) -> Res<Self> {
+        fn str_to_bool(v: &str) -> Res<bool> {
+            match v {
+                "yes" | "true" => Ok(true),
+                "no" | "false" => Ok(false),
+                _ => Err(PropertyError::parse_fail("invalid bool value")),
+            }
+        }
+        match p {
+            Property::B(v) => Ok(v),
+            Property::S(v) => str_to_bool(v),
+            Property::O(v) => str_to_bool(&v),
+            _ => Err(PropertyError::parse_fail("can not num to bool")),
+        }
+    }
+}
+
+impl IsProperty for char {
+    #[inline]
+    fn from_property(p: Property<'_>) -> Res<Self> {
+        let tmp;
+        let v = match p {
+            // String values are checked without copying.
+            Property::S(v) => v,
+            Property::O(v) => {
+                tmp = v;
+                &tmp[..]
+            }
+            p => {
+                tmp = String::from_property(p)?;
+                &tmp[..]
+            }
+        };
+        let mut chars = v.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(PropertyError::parse_fail(
+                "char requires exactly one character",
+            )),
+        }
+    }
+}
+
+macro_rules! impl_property_num {
+    ($($x:ident),+) => {$(
+            impl IsProperty for $x {
+                #[inline]
+                fn from_property(p: Property<'_>) -> Res<Self> {
+                    use core::convert::TryFrom;
+                    Ok(match p {
+                    Property::S(s) => s.parse::<$x>()?,
+                    Property::O(s) => s.parse::<$x>()?,
+                    Property::I(s) => $x::try_from(s)?,
+                    Property::F(s) => check_f64(s)? as $x,
+                    _ => return Err(PropertyError::parse_fail("can not convert bool to num")),
+                    })
+                }
+
+            }
+
+            )+}
+}
+
+impl_property_num!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128, isize, usize);
+
+macro_rules! impl_property_nonzero {
+    ($($x:ident => $y:ident),+) => {$(
+            impl IsProperty for $x {
+                #[inline]
+                fn from_property(p: Property<'_>) -> Res<Self> {
+                    $x::new($y::from_property(p)?)
+                        .ok_or_else(|| PropertyError::parse_fail("zero is not allowed"))
+                }
+
+            }
+
+            )+}
+}
+
+impl_property_nonzero!(
+    NonZeroI8 => i8,
+    NonZeroI16 => i16,
+    NonZeroI32 => i32,
+    NonZeroI64 => i64,
+    NonZeroI128 => i128,
+    NonZeroIsize => isize,
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128,
+    NonZeroUsize => usize
+);
+
+macro_rules! impl_property_float {
+    ($($x:ident),+) => {$(
+            #[allow(trivial_numeric_casts)]
+            impl IsProperty for $x {
+                #[inline]
+                fn from_property(p: Property<'_>) -> Res<Self> {
+                    Ok(match p {
+                    Property::S(s) => check_f64(s.parse::<$x>()? as f64)? as $x,
+                    Property::O(s) => check_f64(s.parse::<$x>()? as f64)? as $x,
+                    Property::I(s) => s as $x,
+                    Property::F(s) => check_f64(s)? as $x,
+                    _ => return Err(PropertyError::parse_fail("can not convert bool to num")),
+                    })
+                }
+
+            }
+
+            )+}
+}
+
+impl_property_float!(f32, f64);
@@ -17,7 +17,10 @@ impl<T: EnumProperty> IsProperty for T {
         match p {
             Property::S(v) => T::str_to_enum(v),
             Property::O(v) => T::str_to_enum(&v),
-            _ => Err(PropertyError::parse_fail("only string can convert to enum")),
+            Property::I(v) => T::str_to_enum(&v.to_string()),
+            _ => Err(PropertyError::parse_fail(
+                "only string or number can convert to enum",
+            )),
         }
     }
 }
@@ -30,7 +33,11 @@ macro_rules! impl_enum_property {
             fn str_to_enum(val: &str) -> Result<$x, $crate::PropertyError> {
                 match &val.to_lowercase()[..] {
                     $($k => Ok($v),)+
-                    _ => Err($crate::PropertyError::parse_fail("invalid enum value")),
+                    _ => Err($crate::PropertyError::parse_fail(&format!(
+                        "invalid enum value '{}', expected one of: {}",
+                        val,
+                        [$($k),+].join(", ")
+                    ))),
                 }
             }
         }
@@ -0,0 +1,87 @@
+use std::{ops::Deref, path::PathBuf};
+
+#[cfg(feature = "derive")]
+use crate::{DescFromEnvironment, SalakDescContext};
+use crate::{FromEnvironment, Property, PropertyError, Res, SalakContext};
+
+/// A wrapper of `T`, which can be read from file if `<key>.file` is set, such as
+/// `db.password.file = /var/run/secrets/db` for `db.password`. File content is trimmed
+/// before parsing, and it takes precedence over value of `<key>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FromFile<T>(T);
+
+impl<T> FromFile<T> {
+    /// Get inner value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for FromFile<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: FromEnvironment> FromEnvironment for FromFile<T> {
+    fn from_env(val: Option<Property<'_>>, env: &mut SalakContext<'_>) -> Res<Self> {
+        if let Some(path) = env.require_def::<Option<PathBuf>>("file", None)? {
+            let content = std::fs::read_to_string(&path).map_err(|e| {
+                PropertyError::parse_fail(&format!("can not read file {}: {}", path.display(), e))
+            })?;
+            return Ok(FromFile(T::from_env(
+                Some(Property::S(content.trim())),
+                env,
+            )?));
+        }
+        Ok(FromFile(T::from_env(val, env)?))
+    }
+}
+
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+impl<T: DescFromEnvironment> DescFromEnvironment for FromFile<T> {
+    #[inline]
+    fn key_desc(env: &mut SalakDescContext<'_>) {
+        T::key_desc(env);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{wrapper::FromFile, *};
+
+    #[test]
+    fn from_file_test() {
+        let path =
+            std::env::temp_dir().join(format!("salak_from_file_test_{}", std::process::id()));
+        std::fs::write(&path, " secret\n").unwrap();
+        let env = Salak::builder()
+            .set("db.password.file", path.display().to_string())
+            .set("db.port", "5432")
+            .set("db.user", "salak")
+            .set("db.user.file", path.display().to_string())
+            .set("db.missing.file", "/salak/not_exists/secret")
+            .build()
+            .unwrap();
+        let password = env.require::<FromFile<String>>("db.password").unwrap();
+        assert_eq!("secret", password.as_str());
+        assert_eq!(5432, *env.require::<FromFile<u16>>("db.port").unwrap());
+        assert_eq!(
+            "secret",
+            env.require::<FromFile<String>>("db.user")
+                .unwrap()
+                .into_inner()
+        );
+        assert_eq!(
+            None,
+            env.require::<Option<FromFile<String>>>("db.none").unwrap()
+        );
+        let err = env.require::<FromFile<String>>("db.missing").unwrap_err();
+        assert!(format!("{:?}", err).contains("can not read file"));
+    }
+}
@@ -1,5 +1,9 @@
 use parking_lot::Mutex;
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    mpsc::Sender,
+    Arc,
+};
 
 use crate::{
     source_raw::PropertyRegistryInternal, FromEnvironment, Property, Res, SalakContext, Void,
@@ -13,14 +17,28 @@ use crate::{DescFromEnvironment, SalakDescContext};
 pub struct IORef<T>(pub(crate) Arc<Mutex<T>>, pub(crate) String);
 
 pub(crate) trait IORefT: Send {
+    /// Key of the tracked value.
+    fn key(&self) -> &str;
+
     fn reload_ref(
         &self,
         env: &PropertyRegistryInternal<'_>,
         ioref: &Mutex<Vec<Box<dyn IORefT + Send>>>,
     ) -> Void;
+
+    /// Check whether the value is still tracked, values not tracked are removed after reloading.
+    #[inline]
+    fn is_alive(&self) -> bool {
+        true
+    }
 }
 
 impl<T: Send + Clone + FromEnvironment> IORefT for IORef<T> {
+    #[inline]
+    fn key(&self) -> &str {
+        &self.1
+    }
+
     #[inline]
     fn reload_ref(
         &self,
@@ -51,6 +69,58 @@ impl<T: Clone> IORef<T> {
     }
 }
 
+/// Send value of key to channel when it is changed by reloading.
+pub(crate) struct KeyWatcher<T> {
+    key: String,
+    last: Mutex<T>,
+    sender: Mutex<Sender<T>>,
+    /// False after receiver is dropped.
+    alive: AtomicBool,
+}
+
+impl<T> KeyWatcher<T> {
+    #[inline]
+    pub(crate) fn new(key: &str, val: T, sender: Sender<T>) -> Self {
+        Self {
+            key: key.to_string(),
+            last: Mutex::new(val),
+            sender: Mutex::new(sender),
+            alive: AtomicBool::new(true),
+        }
+    }
+}
+
+impl<T: Send + Clone + PartialEq + FromEnvironment> IORefT for KeyWatcher<T> {
+    #[inline]
+    fn key(&self) -> &str {
+        &self.key
+    }
+
+    fn reload_ref(
+        &self,
+        env: &PropertyRegistryInternal<'_>,
+        ioref: &Mutex<Vec<Box<dyn IORefT + Send>>>,
+    ) -> Void {
+        // Values fail to parse are skipped, the last value is kept for comparing.
+        if let Ok(val) = env.require::<T>(&self.key, ioref) {
+            let mut last = self.last.lock();
+            if *last != val {
+                *last = val.clone();
+                // Receiver is dropped, then the watcher is removed.
+                if self.sender.lock().send(val).is_err() {
+                    self.alive.store(false, Ordering::Relaxed);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::Relaxed)
+    }
+}
+
 impl<T> FromEnvironment for IORef<T>
 where
     T: Clone + FromEnvironment + Send + 'static,
@@ -73,3 +143,73 @@ where
         T::key_desc(env);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use parking_lot::Mutex;
+    use std::sync::{mpsc::TryRecvError, Arc};
+
+    use crate::{
+        source::{Key, SubKeys},
+        *,
+    };
+
+    #[derive(Clone)]
+    struct Shared(Arc<Mutex<String>>);
+
+    impl PropertySource for Shared {
+        fn name(&self) -> &str {
+            "shared"
+        }
+
+        fn get_property(&self, _: &Key<'_>) -> Option<Property<'_>> {
+            Some(Property::O(self.0.lock().clone()))
+        }
+
+        fn get_sub_keys<'a>(&'a self, _: &Key<'_>, _: &mut SubKeys<'a>) {}
+
+        fn is_empty(&self) -> bool {
+            false
+        }
+
+        fn reload_source(&self) -> Result<Option<Box<dyn PropertySource>>, PropertyError> {
+            Ok(Some(Box::new(self.clone())))
+        }
+    }
+
+    #[test]
+    fn watch_key_test() {
+        let value = Arc::new(Mutex::new("1".to_string()));
+        let mut env = Salak::builder().build().unwrap();
+        env.register(Shared(value.clone()));
+        let rx = env.watch_key::<u8>("port").unwrap();
+        let set = |v: &str| *value.lock() = v.to_string();
+
+        env.reload().unwrap();
+        assert_eq!(Err(TryRecvError::Empty), rx.try_recv());
+
+        set("2");
+        env.reload().unwrap();
+        assert_eq!(Ok(2), rx.try_recv());
+        assert_eq!(Err(TryRecvError::Empty), rx.try_recv());
+
+        set("x");
+        env.reload().unwrap();
+        assert_eq!(Err(TryRecvError::Empty), rx.try_recv());
+
+        set("3");
+        env.reload_detailed().unwrap();
+        assert_eq!(Ok(3), rx.try_recv());
+
+        let watchers = env.ior.lock().len();
+        assert!(env.tracked.lock().contains_key("port"));
+        drop(rx);
+        set("4");
+        env.reload().unwrap();
+        assert_eq!(watchers - 1, env.ior.lock().len());
+        assert!(env.tracked.lock().get("port").is_none());
+
+        set("x");
+        assert!(env.watch_key::<u8>("port").is_err());
+    }
+}
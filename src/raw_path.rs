@@ -0,0 +1,132 @@
+use std::{
+    ffi::OsString,
+    ops::Deref,
+    path::{Path, PathBuf},
+};
+
+use crate::{IsProperty, Property, PropertyError, Res};
+
+/// A wrapper of [`PathBuf`], expanding leading `~` to home directory. If `ENV` is true,
+/// environment variables with format `$VAR` or `${VAR}` are also expanded.
+///
+/// Notice that `$` must use escape format in `salak`, such as `\$VAR` or `\$\{VAR\}`,
+/// while `${VAR}` is resolved as placeholder by `salak` before expanding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpandedPath<const ENV: bool = true>(PathBuf);
+
+impl<const ENV: bool> ExpandedPath<ENV> {
+    /// Get [`PathBuf`].
+    #[inline]
+    pub fn into_inner(self) -> PathBuf {
+        self.0
+    }
+
+    fn home() -> Res<OsString> {
+        std::env::var_os("HOME")
+            .or_else(|| std::env::var_os("USERPROFILE"))
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| PropertyError::parse_fail("home directory not found"))
+    }
+
+    fn var(name: &str) -> Res<OsString> {
+        std::env::var_os(name).ok_or_else(|| {
+            PropertyError::parse_fail(&format!("environment variable {} not found", name))
+        })
+    }
+
+    fn parse(path: &str) -> Res<Self> {
+        let mut buf = OsString::new();
+        let mut path = match path.strip_prefix('~') {
+            Some(v) if v.is_empty() || v.starts_with(std::path::is_separator) => {
+                buf.push(Self::home()?);
+                v
+            }
+            _ => path,
+        };
+        if ENV {
+            while let Some(pos) = path.find('$') {
+                buf.push(&path[..pos]);
+                path = &path[pos + 1..];
+                let name = if let Some(v) = path.strip_prefix('{') {
+                    let end = v
+                        .find('}')
+                        .ok_or_else(|| PropertyError::parse_fail("invalid environment variable"))?;
+                    path = &v[end + 1..];
+                    &v[..end]
+                } else {
+                    let end = path
+                        .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                        .unwrap_or(path.len());
+                    let name = &path[..end];
+                    path = &path[end..];
+                    name
+                };
+                if name.is_empty() {
+                    return Err(PropertyError::parse_fail("invalid environment variable"));
+                }
+                buf.push(Self::var(name)?);
+            }
+        }
+        buf.push(path);
+        Ok(Self(buf.into()))
+    }
+}
+
+impl<const ENV: bool> Deref for ExpandedPath<ENV> {
+    type Target = Path;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const ENV: bool> IsProperty for ExpandedPath<ENV> {
+    #[inline]
+    fn from_property(p: Property<'_>) -> Res<Self> {
+        match p {
+            Property::S(v) => Self::parse(v),
+            Property::O(v) => Self::parse(&v),
+            _ => Err(PropertyError::parse_fail("can not convert to path")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{wrapper::ExpandedPath, *};
+    use std::path::PathBuf;
+
+    #[test]
+    fn expanded_path_test() {
+        let home = PathBuf::from(std::env::var_os("HOME").unwrap());
+        let env = Salak::builder()
+            .set("a", "~/x")
+            .set("b", "/var/lib/x")
+            .set("c", "\\$HOME/x")
+            .set("d", "\\$\\{HOME\\}/x")
+            .set("e", "${HOME}/x")
+            .set("f", "~")
+            .set("g", "~x/y")
+            .set("h", "\\$SALAK_EXPANDED_PATH_NOT_EXISTS/x")
+            .set("i", "/x/\\$")
+            .set("j", "\\$\\{x")
+            .build()
+            .unwrap();
+        let path = |k: &str| env.require::<ExpandedPath>(k).map(|v| v.into_inner());
+        assert_eq!(home.join("x"), path("a").unwrap());
+        assert_eq!(PathBuf::from("/var/lib/x"), path("b").unwrap());
+        for k in &["c", "d", "e"] {
+            assert_eq!(home.join("x"), path(k).unwrap());
+        }
+        assert_eq!(home, path("f").unwrap());
+        assert_eq!(PathBuf::from("~x/y"), path("g").unwrap());
+        for k in &["h", "i", "j"] {
+            assert!(path(k).is_err());
+        }
+        assert_eq!(
+            PathBuf::from("$HOME/x"),
+            *env.require::<ExpandedPath<false>>("c").unwrap()
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use std::ops::Deref;
+
+use crate::{IsProperty, Property, PropertyError, Res};
+
+/// A wrapper of byte size in [`u64`], parsing human readable sizes such as `1024`, `10KB` or `2MiB`.
+/// SI units `KB`, `MB`, `GB`, `TB`, `PB` are powers of 1000, and binary units `KiB`, `MiB`,
+/// `GiB`, `TiB`, `PiB` are powers of 1024. Units are case insensitive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteSize(u64);
+
+impl ByteSize {
+    /// Get byte count.
+    #[inline]
+    pub fn into_inner(self) -> u64 {
+        self.0
+    }
+
+    fn parse(size: &str) -> Res<Self> {
+        let size = size.trim();
+        let pos = size
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(size.len());
+        let (num, unit) = (&size[..pos], size[pos..].trim_start());
+        let num = num
+            .parse::<u64>()
+            .map_err(|_| PropertyError::parse_fail("invalid byte size"))?;
+        let scale: u64 = match unit.to_ascii_lowercase().as_str() {
+            "" | "b" => 1,
+            "kb" => 1_000,
+            "mb" => 1_000_000,
+            "gb" => 1_000_000_000,
+            "tb" => 1_000_000_000_000,
+            "pb" => 1_000_000_000_000_000,
+            "kib" => 1 << 10,
+            "mib" => 1 << 20,
+            "gib" => 1 << 30,
+            "tib" => 1 << 40,
+            "pib" => 1 << 50,
+            _ => {
+                return Err(PropertyError::parse_fail(&format!(
+                    "invalid byte size unit {}",
+                    unit
+                )))
+            }
+        };
+        num.checked_mul(scale)
+            .map(Self)
+            .ok_or_else(|| PropertyError::parse_fail("byte size overflow"))
+    }
+}
+
+impl Deref for ByteSize {
+    type Target = u64;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl IsProperty for ByteSize {
+    #[inline]
+    fn from_property(p: Property<'_>) -> Res<Self> {
+        match p {
+            Property::S(v) => Self::parse(v),
+            Property::O(v) => Self::parse(&v),
+            Property::I(v) if v >= 0 => Ok(Self(v as u64)),
+            _ => Err(PropertyError::parse_fail("can not convert to byte size")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{wrapper::ByteSize, *};
+
+    #[test]
+    fn byte_size_test() {
+        let env = Salak::builder()
+            .set("plain", "1024")
+            .set_typed("typed", 2048u32)
+            .set("b", "10B")
+            .set("kb", "10KB")
+            .set("mb", "2 MB")
+            .set("gb", "1gb")
+            .set("kib", "64KiB")
+            .set("mib", "2MiB")
+            .set("gib", "1 GiB")
+            .set("tib", "3tib")
+            .set("bad_unit", "10XB")
+            .set("bad_num", "1.5MB")
+            .set("negative", "-1")
+            .set("overflow", "100000PiB")
+            .build()
+            .unwrap();
+        let size = |k: &str| env.require::<ByteSize>(k).map(|v| v.into_inner());
+        assert_eq!(1024, size("plain").unwrap());
+        assert_eq!(2048, size("typed").unwrap());
+        assert_eq!(10, size("b").unwrap());
+        assert_eq!(10_000, size("kb").unwrap());
+        assert_eq!(2_000_000, size("mb").unwrap());
+        assert_eq!(1_000_000_000, size("gb").unwrap());
+        assert_eq!(64 * 1024, size("kib").unwrap());
+        assert_eq!(2 * 1024 * 1024, size("mib").unwrap());
+        assert_eq!(1 << 30, size("gib").unwrap());
+        assert_eq!(3 << 40, size("tib").unwrap());
+        for k in &["bad_unit", "bad_num", "negative", "overflow"] {
+            assert!(matches!(
+                env.require::<ByteSize>(k),
+                Err(PropertyError::ParseFail(_, _))
+            ));
+        }
+    }
+}
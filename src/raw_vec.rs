@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
     ops::DerefMut,
 };
 
@@ -77,6 +77,12 @@ impl<T: FromEnvironment> FromEnvironment for Vec<T> {
     fn from_env(_: Option<Property<'_>>, env: &mut SalakContext<'_>) -> Res<Self> {
         let mut vs = vec![];
         if let Some(max) = env.get_sub_keys().max() {
+            if env.registry.sparse_vec {
+                for i in 0..=max {
+                    vs.push(env.require_def_internal::<T, usize>(i, None)?);
+                }
+                return Ok(vs);
+            }
             let mut i = 0;
             while let Some(v) = env.require_def_internal::<Option<T>, usize>(i, None)? {
                 vs.push(v);
@@ -122,7 +128,12 @@ impl<T: FromEnvironment> FromEnvironment for HashMap<String, T> {
 impl<T: DescFromEnvironment> DescFromEnvironment for HashMap<String, T> {
     fn key_desc(env: &mut SalakDescContext<'_>) {
         env.current.set_required(false);
-        env.add_key_desc::<T>("*", None, None, env.current.desc.clone());
+        env.add_key_desc::<T>(
+            "<key>",
+            env.current.required,
+            None,
+            env.current.desc.clone(),
+        );
     }
 }
 
@@ -145,3 +156,141 @@ where
         <Vec<T>>::key_desc(env);
     }
 }
+
+impl<T: FromEnvironment> FromEnvironment for VecDeque<T> {
+    fn from_env(val: Option<Property<'_>>, env: &mut SalakContext<'_>) -> Res<Self> {
+        Ok(<Vec<T>>::from_env(val, env)?.into())
+    }
+}
+
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+impl<T: DescFromEnvironment> DescFromEnvironment for VecDeque<T> {
+    fn key_desc(env: &mut SalakDescContext<'_>) {
+        <Vec<T>>::key_desc(env);
+    }
+}
+
+impl<T: Ord + FromEnvironment> FromEnvironment for BinaryHeap<T> {
+    fn from_env(val: Option<Property<'_>>, env: &mut SalakContext<'_>) -> Res<Self> {
+        Ok(<Vec<T>>::from_env(val, env)?.into())
+    }
+}
+
+#[cfg(feature = "derive")]
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+impl<T: Ord + DescFromEnvironment> DescFromEnvironment for BinaryHeap<T> {
+    fn key_desc(env: &mut SalakDescContext<'_>) {
+        <Vec<T>>::key_desc(env);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn collection_desc_test() {
+        #[derive(FromEnvironment)]
+        #[allow(dead_code)]
+        struct Server {
+            host: String,
+            #[salak(default = 80)]
+            port: u16,
+        }
+
+        #[derive(FromEnvironment)]
+        #[salak(prefix = "cluster")]
+        #[allow(dead_code)]
+        struct Cluster {
+            servers: Vec<Server>,
+            named: std::collections::HashMap<String, Server>,
+            weights: std::collections::HashMap<String, u8>,
+        }
+
+        let env = Salak::builder().build().unwrap();
+        let desc = env.describe_namespaced::<Cluster>("");
+        let desc: Vec<(&str, &str, bool)> = desc
+            .iter()
+            .map(|d| (d.key(), d.type_name(), d.required()))
+            .collect();
+        assert_eq!(
+            vec![
+                (
+                    "cluster.servers[*].host",
+                    std::any::type_name::<String>(),
+                    true
+                ),
+                ("cluster.servers[*].port", "u16", false),
+                (
+                    "cluster.named.<key>.host",
+                    std::any::type_name::<String>(),
+                    true
+                ),
+                ("cluster.named.<key>.port", "u16", false),
+                ("cluster.weights.<key>", "u8", false),
+            ],
+            desc
+        );
+    }
+
+    #[test]
+    fn sparse_vec_test() {
+        let build = |sparse: bool| {
+            Salak::builder()
+                .set("dense[0]", "1")
+                .set("dense[1]", "2")
+                .set("sparse[0]", "1")
+                .set("sparse[2]", "3")
+                .configure_sparse_vec(sparse)
+                .build()
+                .unwrap()
+        };
+
+        let env = build(false);
+        assert_eq!(vec![1, 2], env.require::<Vec<u8>>("dense").unwrap());
+        assert_eq!(vec![1], env.require::<Vec<u8>>("sparse").unwrap());
+        assert_eq!(
+            vec![Some(1), None, Some(3)],
+            env.require::<Vec<Option<u8>>>("sparse").unwrap()
+        );
+
+        let env = build(true);
+        assert_eq!(vec![1, 2], env.require::<Vec<u8>>("dense").unwrap());
+        assert_eq!(
+            "Err(NotFound(\"sparse[1]\"))",
+            format!("{:?}", env.require::<Vec<u8>>("sparse"))
+        );
+        assert_eq!(
+            vec![Some(1), None, Some(3)],
+            env.require::<Vec<Option<u8>>>("sparse").unwrap()
+        );
+        assert!(env.require::<Vec<u8>>("none").unwrap().is_empty());
+    }
+
+    #[test]
+    fn queue_test() {
+        let env = Salak::builder()
+            .set("q[0]", "3")
+            .set("q[1]", "1")
+            .set("q[2]", "4")
+            .set("q[3]", "2")
+            .build()
+            .unwrap();
+        let mut deque = env.require::<std::collections::VecDeque<u8>>("q").unwrap();
+        assert_eq!(Some(3), deque.pop_front());
+        assert_eq!(Some(2), deque.pop_back());
+        assert_eq!(vec![1, 4], deque.into_iter().collect::<Vec<_>>());
+
+        let heap = env
+            .require::<std::collections::BinaryHeap<u8>>("q")
+            .unwrap();
+        assert_eq!(Some(&4), heap.peek());
+        assert_eq!(vec![1, 2, 3, 4], heap.into_sorted_vec());
+        assert!(env
+            .require::<std::collections::BinaryHeap<u8>>("none")
+            .unwrap()
+            .is_empty());
+    }
+}
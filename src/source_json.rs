@@ -0,0 +1,171 @@
+use serde_json::Value;
+
+use crate::{Key, Property, PropertySource, SubKey, SubKeys};
+
+/// Json value as [`PropertySource`], which also provides subtrees as structured values,
+/// see [`PropertySource::get_json()`].
+///
+/// Explicit `null` is treated as missing like other sources, so defaults still apply.
+#[derive(Debug)]
+pub struct JsonSource {
+    name: String,
+    value: Value,
+}
+
+impl JsonSource {
+    /// Create source from json value.
+    pub fn new(name: &str, value: Value) -> Self {
+        Self {
+            name: name.to_string(),
+            value,
+        }
+    }
+}
+
+impl PropertySource for JsonSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_property(&self, key: &Key<'_>) -> Option<Property<'_>> {
+        match self.get_json(key)? {
+            Value::String(v) => Some(Property::S(v)),
+            Value::Number(v) => Some(match (v.as_i64(), v.as_u64()) {
+                (Some(v), _) => Property::I(v.into()),
+                (_, Some(v)) => Property::I(v.into()),
+                _ => Property::F(v.as_f64()?),
+            }),
+            Value::Bool(v) => Some(Property::B(*v)),
+            _ => None,
+        }
+    }
+
+    fn get_sub_keys<'a>(&'a self, key: &Key<'_>, sub_keys: &mut SubKeys<'a>) {
+        match self.get_json(key) {
+            Some(Value::Object(t)) => t.keys().for_each(|f| sub_keys.insert(f.as_str())),
+            Some(Value::Array(vs)) if !vs.is_empty() => sub_keys.insert(vs.len() - 1),
+            _ => {}
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match &self.value {
+            Value::Object(t) => t.is_empty(),
+            _ => false,
+        }
+    }
+
+    fn get_json<'a>(&'a self, key: &Key<'_>) -> Option<&'a Value> {
+        let mut val = &self.value;
+        for n in key.iter() {
+            val = match (n, val) {
+                (SubKey::S(n), Value::Object(t)) => t.get(n)?,
+                (SubKey::I(n), Value::Array(vs)) => vs.get(n)?,
+                _ => return None,
+            };
+        }
+        Some(val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        source::{HashMapSource, JsonSource},
+        *,
+    };
+    use serde_json::json;
+
+    fn doc() -> serde_json::Value {
+        json!({
+            "host": "localhost",
+            "port": 5432,
+            "ratio": 0.5,
+            "ssl": true,
+            "replicas": [{"host": "r0"}, {"host": "r1", "port": 5433}],
+            "tags": ["a", "b"],
+        })
+    }
+
+    fn env(source: impl PropertySource + 'static) -> Salak {
+        let mut env = Salak::builder().disable_system_env().build().unwrap();
+        env.register(source);
+        env
+    }
+
+    #[test]
+    fn structured_test() {
+        let flattened = env(HashMapSource::new("flattened")
+            .set("db.host", "localhost")
+            .set_typed("db.port", 5432u16)
+            .set_typed("db.ratio", 0.5f64)
+            .set_typed("db.ssl", true)
+            .set("db.replicas[0].host", "r0")
+            .set("db.replicas[1].host", "r1")
+            .set_typed("db.replicas[1].port", 5433u16)
+            .set("db.tags[0]", "a")
+            .set("db.tags[1]", "b"));
+        let structured = env(JsonSource::new("structured", json!({ "db": doc() })));
+
+        let source = JsonSource::new("structured", json!({ "db": doc() }));
+        assert_eq!(Some(&doc()), source.get_json(&Key::from_str("db")));
+        assert_eq!(
+            Some(&json!("r1")),
+            source.get_json(&Key::from_str("db.replicas[1].host"))
+        );
+
+        for key in &["db", "db.replicas", "db.replicas[1]", "db.tags[0]", "none"] {
+            assert_eq!(
+                flattened.require::<Option<serde_json::Value>>(key).unwrap(),
+                structured
+                    .require::<Option<serde_json::Value>>(key)
+                    .unwrap()
+            );
+        }
+        assert_eq!(
+            doc(),
+            structured.require::<serde_json::Value>("db").unwrap()
+        );
+        assert_eq!(
+            5433,
+            structured.require::<u16>("db.replicas[1].port").unwrap()
+        );
+        assert_eq!(
+            vec!["a", "b"],
+            structured.require::<Vec<String>>("db.tags").unwrap()
+        );
+        assert!(structured.require::<serde_json::Value>("none").is_err());
+    }
+
+    #[test]
+    fn null_test() {
+        let mut env = Salak::builder().disable_system_env().build().unwrap();
+        env.register(JsonSource::new("json", json!({"a": null, "b": null})));
+        env.register(
+            HashMapSource::new("default")
+                .set("a", "default")
+                .set("port", "${b:8080}"),
+        );
+        assert_eq!("default", env.require::<String>("a").unwrap());
+        assert_eq!(None, env.require::<Option<String>>("b").unwrap());
+        assert_eq!(8080, env.require::<u16>("port").unwrap());
+    }
+
+    #[test]
+    fn structured_fallback_test() {
+        // Placeholders are resolved and other sources are merged by flattened reads.
+        let mut env = Salak::builder()
+            .disable_system_env()
+            .set("db.port", "5433")
+            .build()
+            .unwrap();
+        env.register(JsonSource::new(
+            "structured",
+            json!({"db": {"host": "localhost", "port": 5432, "url": "http://${db.host}"}}),
+        ));
+        assert_eq!(
+            json!({"host": "localhost", "port": "5433", "url": "http://localhost"}),
+            env.require::<serde_json::Value>("db").unwrap()
+        );
+    }
+}
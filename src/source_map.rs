@@ -1,12 +1,13 @@
-use std::collections::HashMap;
+use parking_lot::RwLock;
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
-use crate::{Key, Property, PropertySource, SubKeys};
+use crate::{Key, Property, PropertySource, Res, SubKeys};
 
-/// An in-memory source, which is a string to string hashmap.
-#[derive(Debug)]
+/// An in-memory source, which is a string to property hashmap.
+#[derive(Debug, Clone)]
 pub struct HashMapSource {
     name: String,
-    map: HashMap<String, String>,
+    map: HashMap<String, Property<'static>>,
 }
 
 impl HashMapSource {
@@ -18,17 +19,60 @@ impl HashMapSource {
         }
     }
 
+    pub(crate) fn with_map(name: &str, map: HashMap<String, Property<'static>>) -> Self {
+        Self {
+            name: name.to_owned(),
+            map,
+        }
+    }
+
     /// Set property to the source.
     pub fn set<K: Into<String>, V: Into<String>>(mut self, key: K, val: V) -> Self {
+        self.map.insert(key.into(), Property::O(val.into()));
+        self
+    }
+
+    /// Set property with its native type to the source.
+    pub fn set_typed<K: Into<String>, V: Into<Property<'static>>>(
+        mut self,
+        key: K,
+        val: V,
+    ) -> Self {
         self.map.insert(key.into(), val.into());
         self
     }
 
+    /// Set property to the source in place. Registered source can not be mutated,
+    /// use [`HashMapSource::into_shared()`] to mutate it after registered.
+    pub fn set_mut<K: Into<String>, V: Into<String>>(&mut self, key: K, val: V) {
+        self.map.insert(key.into(), Property::O(val.into()));
+    }
+
+    /// Remove property from the source, the removed property is returned.
+    pub fn remove(&mut self, key: &str) -> Option<Property<'static>> {
+        self.map.remove(key)
+    }
+
     /// Set a batch of properties to the source.
     pub fn set_all(mut self, map: HashMap<String, String>) -> Self {
+        self.map
+            .extend(map.into_iter().map(|(k, v)| (k, Property::O(v))));
+        self
+    }
+
+    /// Set a batch of typed properties to the source.
+    pub fn set_all_typed(mut self, map: HashMap<String, Property<'static>>) -> Self {
         self.map.extend(map);
         self
     }
+
+    /// Convert into [`SharedMapSource`], which can be mutated after registered.
+    pub fn into_shared(self) -> SharedMapSource {
+        SharedMapSource {
+            shared: Arc::new(RwLock::new(self.clone())),
+            current: Arc::new(self),
+        }
+    }
 }
 
 impl PropertySource for HashMapSource {
@@ -39,16 +83,24 @@ impl PropertySource for HashMapSource {
 
     #[inline]
     fn get_property(&self, key: &Key<'_>) -> Option<Property<'_>> {
-        self.map.get(key.as_str()).map(|s| Property::S(s))
+        self.map.get(key.as_str()).map(|p| match p {
+            Property::S(v) => Property::S(v),
+            Property::O(v) => Property::S(v),
+            Property::I(v) => Property::I(*v),
+            Property::F(v) => Property::F(*v),
+            Property::B(v) => Property::B(*v),
+        })
+    }
+
+    #[inline]
+    fn contains(&self, key: &Key<'_>) -> bool {
+        self.map.contains_key(key.as_str())
     }
 
     fn get_sub_keys<'a>(&'a self, prefix: &Key<'_>, sub_keys: &mut SubKeys<'a>) {
-        for key in self.map.keys() {
-            if let Some(k) = key.strip_prefix(prefix.as_str()) {
-                let pos = k.find('.').unwrap_or_else(|| k.len());
-                sub_keys.insert(&k[0..pos]);
-            }
-        }
+        for_sub_keys(self.map.keys().map(String::as_str), prefix, |k| {
+            sub_keys.insert(k)
+        })
     }
 
     fn is_empty(&self) -> bool {
@@ -56,10 +108,472 @@ impl PropertySource for HashMapSource {
     }
 }
 
+/// An in-memory source created by [`HashMapSource::into_shared()`], clones share
+/// the same properties. Register one clone and mutate properties by another, changes
+/// take effect after [`crate::Environment::reload()`].
+#[derive(Debug, Clone)]
+pub struct SharedMapSource {
+    shared: Arc<RwLock<HashMapSource>>,
+    current: Arc<HashMapSource>,
+}
+
+impl SharedMapSource {
+    /// Set property, which takes effect after reloading.
+    pub fn set<K: Into<String>, V: Into<String>>(&self, key: K, val: V) {
+        self.shared.write().set_mut(key, val);
+    }
+
+    /// Remove property, the removed property is returned. It takes effect after reloading.
+    pub fn remove(&self, key: &str) -> Option<Property<'static>> {
+        self.shared.write().remove(key)
+    }
+}
+
+impl PropertySource for SharedMapSource {
+    #[inline]
+    fn name(&self) -> &str {
+        self.current.name()
+    }
+
+    #[inline]
+    fn get_property(&self, key: &Key<'_>) -> Option<Property<'_>> {
+        self.current.get_property(key)
+    }
+
+    #[inline]
+    fn contains(&self, key: &Key<'_>) -> bool {
+        self.current.contains(key)
+    }
+
+    #[inline]
+    fn get_sub_keys<'a>(&'a self, key: &Key<'_>, sub_keys: &mut SubKeys<'a>) {
+        self.current.get_sub_keys(key, sub_keys)
+    }
+
+    /// Properties may be set later, so it is never empty.
+    fn is_empty(&self) -> bool {
+        false
+    }
+
+    fn reload_source(&self) -> Res<Option<Box<dyn PropertySource>>> {
+        Ok(Some(Box::new(SharedMapSource {
+            shared: self.shared.clone(),
+            current: Arc::new(self.shared.read().clone()),
+        })))
+    }
+}
+
+/// Call `f` with sub keys under `prefix` of `keys`.
+fn for_sub_keys<'a>(
+    keys: impl Iterator<Item = &'a str>,
+    prefix: &Key<'_>,
+    mut f: impl FnMut(&'a str),
+) {
+    let prefix = prefix.as_str();
+    for key in keys {
+        let k = match key.strip_prefix(prefix) {
+            Some(k) if prefix.is_empty() || k.starts_with('[') => k,
+            Some(k) => match k.strip_prefix('.') {
+                Some(k) => k,
+                _ => continue,
+            },
+            _ => continue,
+        };
+        let pos = if k.starts_with('[') {
+            k.find(']').map(|p| p + 1)
+        } else {
+            k.find(&['.', '['][..])
+        }
+        .unwrap_or(k.len());
+        if pos > 0 {
+            (f)(&k[0..pos]);
+        }
+    }
+}
+
+/// Mutable in-memory source of runtime overrides, see [`crate::Salak::set_runtime()`].
+///
+/// Sub keys are copied out of the lock.
+#[derive(Debug, Default)]
+pub(crate) struct RuntimeSource {
+    map: RwLock<HashMap<String, Property<'static>>>,
+}
+
+impl RuntimeSource {
+    pub(crate) fn set(&self, key: &str, val: Property<'static>) {
+        let key = Key::from_str(key);
+        let _ = self.map.write().insert(key.as_str().to_string(), val);
+    }
+}
+
+impl PropertySource for RuntimeSource {
+    fn name(&self) -> &str {
+        "Runtime"
+    }
+
+    fn get_property(&self, key: &Key<'_>) -> Option<Property<'_>> {
+        self.map.read().get(key.as_str()).map(|p| match p {
+            Property::S(v) => Property::S(v),
+            Property::O(v) => Property::O(v.clone()),
+            Property::I(v) => Property::I(*v),
+            Property::F(v) => Property::F(*v),
+            Property::B(v) => Property::B(*v),
+        })
+    }
+
+    fn contains(&self, key: &Key<'_>) -> bool {
+        self.map.read().contains_key(key.as_str())
+    }
+
+    fn get_sub_keys<'a>(&'a self, prefix: &Key<'_>, sub_keys: &mut SubKeys<'a>) {
+        for_sub_keys(self.map.read().keys().map(String::as_str), prefix, |k| {
+            sub_keys.insert_owned(k)
+        })
+    }
+
+    /// Never empty, so it is kept registered for later overrides.
+    fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+/// A source combining two sources, created by [`PropertySource::or`].
+/// Properties are get from `A` first, then `B`, and sub keys are merged.
+#[allow(missing_debug_implementations)]
+pub struct OrSource<A, B> {
+    name: String,
+    a: Arc<A>,
+    b: Arc<B>,
+}
+
+impl<A: PropertySource, B: PropertySource> OrSource<A, B> {
+    pub(crate) fn new(a: A, b: B) -> Self {
+        Self {
+            name: format!("{}|{}", a.name(), b.name()),
+            a: Arc::new(a),
+            b: Arc::new(b),
+        }
+    }
+}
+
+impl<P: PropertySource + ?Sized> PropertySource for Arc<P> {
+    #[inline]
+    fn name(&self) -> &str {
+        self.as_ref().name()
+    }
+
+    #[inline]
+    fn get_property(&self, key: &Key<'_>) -> Option<Property<'_>> {
+        self.as_ref().get_property(key)
+    }
+
+    #[inline]
+    fn contains(&self, key: &Key<'_>) -> bool {
+        self.as_ref().contains(key)
+    }
+
+    #[inline]
+    fn get_sub_keys<'a>(&'a self, key: &Key<'_>, sub_keys: &mut SubKeys<'a>) {
+        self.as_ref().get_sub_keys(key, sub_keys)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.as_ref().is_empty()
+    }
+
+    #[inline]
+    fn location(&self, key: &Key<'_>) -> Option<String> {
+        self.as_ref().location(key)
+    }
+
+    #[cfg(feature = "json")]
+    #[inline]
+    fn get_json<'a>(&'a self, key: &Key<'_>) -> Option<&'a serde_json::Value> {
+        self.as_ref().get_json(key)
+    }
+
+    #[inline]
+    fn reload_source(&self) -> Res<Option<Box<dyn PropertySource>>> {
+        self.as_ref().reload_source()
+    }
+
+    #[inline]
+    fn reload_interval(&self) -> Option<Duration> {
+        self.as_ref().reload_interval()
+    }
+}
+
+impl<A: PropertySource + 'static, B: PropertySource + 'static> PropertySource for OrSource<A, B> {
+    #[inline]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    fn get_property(&self, key: &Key<'_>) -> Option<Property<'_>> {
+        self.a
+            .get_property(key)
+            .or_else(|| self.b.get_property(key))
+    }
+
+    #[inline]
+    fn contains(&self, key: &Key<'_>) -> bool {
+        self.a.contains(key) || self.b.contains(key)
+    }
+
+    fn get_sub_keys<'a>(&'a self, key: &Key<'_>, sub_keys: &mut SubKeys<'a>) {
+        self.a.get_sub_keys(key, sub_keys);
+        self.b.get_sub_keys(key, sub_keys);
+    }
+
+    fn is_empty(&self) -> bool {
+        self.a.is_empty() && self.b.is_empty()
+    }
+
+    fn location(&self, key: &Key<'_>) -> Option<String> {
+        if self.a.get_property(key).is_some() {
+            self.a.location(key)
+        } else {
+            self.b.location(key)
+        }
+    }
+
+    /// Reload both sources, unchanged source is shared with the reloaded one.
+    fn reload_source(&self) -> Res<Option<Box<dyn PropertySource>>> {
+        let (a, b) = match (self.a.reload_source()?, self.b.reload_source()?) {
+            (None, None) => return Ok(None),
+            v => v,
+        };
+        let a: Arc<dyn PropertySource> = match a {
+            Some(a) => Arc::from(a),
+            _ => self.a.clone(),
+        };
+        let b: Arc<dyn PropertySource> = match b {
+            Some(b) => Arc::from(b),
+            _ => self.b.clone(),
+        };
+        Ok(Some(Box::new(OrSource {
+            name: self.name.clone(),
+            a: Arc::new(a),
+            b: Arc::new(b),
+        })))
+    }
+
+    fn reload_interval(&self) -> Option<Duration> {
+        match (self.a.reload_interval(), self.b.reload_interval()) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+}
+
+/// Convert environment variable name separated by `__` to key,
+/// numeric segments are treated as indexes, eg. `app__servers__0__host` => `app.servers[0].host`.
+fn env_key(name: &str) -> Option<String> {
+    if !name.contains("__") {
+        return None;
+    }
+    let mut key = String::new();
+    for seg in name.split("__") {
+        if seg.is_empty() {
+            return None;
+        }
+        if seg.chars().all(|c| c.is_ascii_digit()) {
+            if key.is_empty() {
+                return None;
+            }
+            key.push('[');
+            key.push_str(seg);
+            key.push(']');
+        } else {
+            if !key.is_empty() {
+                key.push('.');
+            }
+            key.push_str(seg);
+        }
+    }
+    Some(key)
+}
+
 /// Create source from system environment.
+///
+/// Environment variable names separated by `__` can also be used as keys, numeric segments are
+/// treated as indexes, eg. `servers__0` overrides `servers[0]`, and `app__db__port` overrides `app.db.port`.
 pub fn system_environment() -> HashMapSource {
-    HashMapSource {
-        name: "SystemEnvironment".to_owned(),
-        map: std::env::vars().collect(),
+    environment("SystemEnvironment", std::env::vars())
+}
+
+/// Create environment source from `vars`, names separated by `__` are converted
+/// to keys like [`system_environment`].
+pub(crate) fn environment(
+    name: &str,
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> HashMapSource {
+    let mut map: HashMap<String, Property<'static>> = HashMap::new();
+    let mut alias = vec![];
+    for (k, v) in vars {
+        if let Some(key) = env_key(&k) {
+            alias.push((key, v.clone()));
+        }
+        map.insert(k, Property::O(v));
+    }
+    for (k, v) in alias {
+        map.entry(k).or_insert(Property::O(v));
+    }
+    HashMapSource::with_map(name, map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::env_key;
+    use crate::{source::HashMapSource, *};
+
+    #[test]
+    fn env_key_test() {
+        assert_eq!(None, env_key("PATH"));
+        assert_eq!(None, env_key("a_b"));
+        assert_eq!(Some("a.b".to_string()), env_key("a__b"));
+        assert_eq!(Some("servers[0]".to_string()), env_key("servers__0"));
+        assert_eq!(
+            Some("app.servers[0][1].host".to_string()),
+            env_key("app__servers__0__1__host")
+        );
+        assert_eq!(None, env_key("__a"));
+        assert_eq!(None, env_key("a____b"));
+        assert_eq!(None, env_key("0__a"));
+    }
+
+    #[test]
+    fn or_source_test() {
+        let source = HashMapSource::new("a")
+            .set("a", "1")
+            .set("m.x", "a")
+            .or(HashMapSource::new("b")
+                .set("a", "2")
+                .set("b", "3")
+                .set("m.y", "b"));
+        assert_eq!("a|b", source.name());
+        let mut env = Salak::builder().disable_system_env().build().unwrap();
+        env.register(source);
+        assert_eq!(1, env.require::<u8>("a").unwrap());
+        assert_eq!(3, env.require::<u8>("b").unwrap());
+        let m = env
+            .require::<std::collections::HashMap<String, String>>("m")
+            .unwrap();
+        assert_eq!(2, m.len());
+        assert_eq!("a", m["x"]);
+        assert_eq!("b", m["y"]);
+
+        assert!(HashMapSource::new("a")
+            .or(HashMapSource::new("b"))
+            .is_empty());
+    }
+
+    fn synthetic_env(port: &str, replace: bool) -> Salak {
+        let mut vars = std::collections::HashMap::new();
+        vars.insert("synthetic__port".to_string(), port.to_string());
+        let builder = Salak::builder().set("synthetic.host", "localhost");
+        match replace {
+            true => builder.replace_env_map(vars),
+            _ => builder.with_env_map(vars),
+        }
+        .build()
+        .unwrap()
+    }
+
+    #[test]
+    fn set_all_test() {
+        let pairs: &[(&str, &str)] = &[("a", "1"), ("b", "2")];
+        let env = Salak::builder()
+            .set("a", "0")
+            .set_all(pairs.iter().copied())
+            .set_all(vec![("c".to_string(), "3")])
+            .build()
+            .unwrap();
+        assert_eq!(1, env.require::<u8>("a").unwrap());
+        assert_eq!(2, env.require::<u8>("b").unwrap());
+        assert_eq!(3, env.require::<u8>("c").unwrap());
+    }
+
+    #[test]
+    fn mutable_source_test() {
+        let mut source = HashMapSource::new("map").set("a", "1");
+        source.set_mut("b", "2");
+        assert!(source.remove("a").is_some());
+        assert!(source.remove("a").is_none());
+        assert!(source.get_property(&Key::from_str("a")).is_none());
+        assert!(source.get_property(&Key::from_str("b")).is_some());
+    }
+
+    #[test]
+    fn shared_source_test() {
+        let source = HashMapSource::new("shared")
+            .set("a", "1")
+            .set("b", "2")
+            .into_shared();
+        let mut env = Salak::builder().build().unwrap();
+        env.register(source.clone());
+        let a = env.require::<wrapper::IORef<u8>>("a").unwrap();
+        let b = env.require::<wrapper::IORef<Option<u8>>>("b").unwrap();
+        let c = env.require::<wrapper::IORef<Option<u8>>>("c").unwrap();
+        assert_eq!(1, a.get_val().unwrap());
+        assert_eq!(Some(2), b.get_val().unwrap());
+
+        source.set("a", "3");
+        assert!(source.remove("b").is_some());
+        assert!(source.remove("b").is_none());
+        source.set("c", "4");
+        assert_eq!(1, a.get_val().unwrap());
+        env.reload().unwrap();
+        assert_eq!(3, a.get_val().unwrap());
+        assert_eq!(None, b.get_val().unwrap());
+        assert_eq!(Some(4), c.get_val().unwrap());
+
+        // Empty shared source is registered, so it can be filled later.
+        let source = HashMapSource::new("empty").into_shared();
+        let mut env = Salak::builder().build().unwrap();
+        env.register(source.clone());
+        let d = env.require::<wrapper::IORef<Option<u8>>>("d").unwrap();
+        source.set("d", "5");
+        env.reload().unwrap();
+        assert_eq!(Some(5), d.get_val().unwrap());
+    }
+
+    #[test]
+    fn with_env_map_test() {
+        let env = synthetic_env("80", true);
+        assert_eq!(80, env.require::<u16>("synthetic.port").unwrap());
+        assert_eq!("80", env.require::<String>("synthetic__port").unwrap());
+        assert!(!env.contains("PATH"));
+
+        let env = synthetic_env("81", false);
+        assert_eq!(81, env.require::<u16>("synthetic.port").unwrap());
+        assert_eq!(std::env::var("PATH").is_ok(), env.contains("PATH"));
+
+        // Synthetic variables override system environment.
+        let mut vars = std::collections::HashMap::new();
+        vars.insert("PATH".to_string(), "synthetic".to_string());
+        let env = Salak::builder().with_env_map(vars).build().unwrap();
+        assert_eq!("synthetic", env.require::<String>("PATH").unwrap());
+    }
+
+    #[test]
+    fn with_env_map_parallel_test() {
+        let handles: Vec<_> = (0..4u16)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    let env = synthetic_env(&i.to_string(), true);
+                    assert_eq!(i, env.require::<u16>("synthetic.port").unwrap());
+                    assert_eq!(
+                        "localhost",
+                        env.require::<String>("synthetic.host").unwrap()
+                    );
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
     }
 }
@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+
+use crate::{
+    source::HashMapSource,
+    source_raw::{FileItem, FileStamp},
+    Key, Property, PropertyError, PropertySource, Res, SubKeys,
+};
+
+/// Java style `.properties` file source, keys such as `a.b.0` are used as `a.b[0]`.
+#[derive(Debug)]
+pub(crate) struct Properties {
+    item: FileItem,
+    stamp: FileStamp,
+    source: HashMapSource,
+    lines: HashMap<String, usize>,
+}
+
+impl Properties {
+    pub(crate) fn new(item: FileItem) -> Res<Self> {
+        let stamp = FileStamp::new(&item);
+        let content = item.load()?;
+        let mut map = HashMap::new();
+        let mut lines = HashMap::new();
+        for (line, key, val) in parse(&content)? {
+            let key = Key::from_str(&key).as_str().to_string();
+            lines.insert(key.clone(), line);
+            map.insert(key, Property::O(val));
+        }
+        Ok(Self {
+            source: HashMapSource::with_map(&item.name(), map),
+            stamp,
+            lines,
+            item,
+        })
+    }
+}
+
+/// Whitespace separating key and value.
+const WHITESPACE: &[char] = &[' ', '\t', '\x0c'];
+
+/// Parse properties content into `(line, key, value)`, later keys override earlier ones.
+fn parse(content: &str) -> Res<Vec<(usize, String, String)>> {
+    let mut props = vec![];
+    let mut lines = content.lines().enumerate();
+    while let Some((i, mut line)) = lines.next() {
+        line = line.trim_start_matches(WHITESPACE);
+        if line.is_empty() || line.starts_with(['#', '!']) {
+            continue;
+        }
+        // Line ending with odd number of backslashes continues on next line,
+        // leading whitespace of next line is ignored.
+        let mut logical = String::new();
+        while line.chars().rev().take_while(|c| *c == '\\').count() % 2 == 1 {
+            logical.push_str(&line[..line.len() - 1]);
+            line = match lines.next() {
+                Some((_, next)) => next.trim_start_matches(WHITESPACE),
+                _ => "",
+            };
+        }
+        logical.push_str(line);
+        let (key, val) = split(&logical);
+        props.push((i + 1, unescape(key)?, unescape(val)?));
+    }
+    Ok(props)
+}
+
+/// Split line into key and value, key ends at the first unescaped `=`, `:` or whitespace.
+fn split(line: &str) -> (&str, &str) {
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '=' | ':' | ' ' | '\t' | '\x0c' => {
+                let val = line[i..].trim_start_matches(WHITESPACE);
+                let val = match val.strip_prefix(['=', ':']) {
+                    Some(v) => v.trim_start_matches(WHITESPACE),
+                    _ => val,
+                };
+                return (&line[..i], val);
+            }
+            _ => {}
+        }
+    }
+    (line, "")
+}
+
+/// Unescape `\t`, `\n`, `\r`, `\f` and `\uXXXX`, surrogate pairs are supported.
+/// Other escaped characters are kept as is, such as `\=` => `=`.
+fn unescape(v: &str) -> Res<String> {
+    fn flush(units: &mut Vec<u16>, s: &mut String) -> Res<()> {
+        if !units.is_empty() {
+            s.push_str(
+                &String::from_utf16(units)
+                    .map_err(|_| PropertyError::parse_fail("invalid unicode escape"))?,
+            );
+            units.clear();
+        }
+        Ok(())
+    }
+
+    let mut s = String::with_capacity(v.len());
+    let mut units = vec![];
+    let mut chars = v.chars();
+    while let Some(c) = chars.next() {
+        let c = match c {
+            '\\' => match chars.next() {
+                Some('u') => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    match u16::from_str_radix(&hex, 16) {
+                        Ok(u) if hex.len() == 4 => units.push(u),
+                        _ => {
+                            return Err(PropertyError::parse_fail(&format!(
+                                "invalid unicode escape \\u{}",
+                                hex
+                            )))
+                        }
+                    }
+                    continue;
+                }
+                Some('t') => '\t',
+                Some('n') => '\n',
+                Some('r') => '\r',
+                Some('f') => '\x0c',
+                Some(c) => c,
+                _ => break,
+            },
+            c => c,
+        };
+        flush(&mut units, &mut s)?;
+        s.push(c);
+    }
+    flush(&mut units, &mut s)?;
+    Ok(s)
+}
+
+impl PropertySource for Properties {
+    fn name(&self) -> &str {
+        self.source.name()
+    }
+
+    fn get_property(&self, key: &Key<'_>) -> Option<Property<'_>> {
+        self.source.get_property(key)
+    }
+
+    fn contains(&self, key: &Key<'_>) -> bool {
+        self.source.contains(key)
+    }
+
+    fn get_sub_keys<'a>(&'a self, key: &Key<'_>, sub_keys: &mut SubKeys<'a>) {
+        self.source.get_sub_keys(key, sub_keys)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.source.is_empty()
+    }
+
+    fn location(&self, key: &Key<'_>) -> Option<String> {
+        let line = self.lines.get(key.as_str())?;
+        Some(format!("{}:{}", self.name(), line))
+    }
+
+    fn reload_source(&self) -> Result<Option<Box<dyn PropertySource>>, PropertyError> {
+        self.stamp.reload(&self.item, Properties::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn parse_test() {
+        let content = "# comment\n\
+            ! another comment\n\
+            \n\
+            a = 1\n\
+            b:2\n\
+            c 3\n\
+            \x20\x20d=\n\
+            e\n\
+            f\\:g = h\\=i\n\
+            long = hello, \\\n\
+            \x20\x20\x20\x20world\n\
+            even = x\\\\\n\
+            hi = \\u4f60\\u597D \\ud83d\\ude00\\tend\n";
+        let props: Vec<(usize, String, String)> = parse(content).unwrap();
+        let expected: Vec<(usize, &str, &str)> = vec![
+            (4, "a", "1"),
+            (5, "b", "2"),
+            (6, "c", "3"),
+            (7, "d", ""),
+            (8, "e", ""),
+            (9, "f:g", "h=i"),
+            (10, "long", "hello, world"),
+            (12, "even", "x\\"),
+            (13, "hi", "你好 😀\tend"),
+        ];
+        assert_eq!(
+            expected,
+            props
+                .iter()
+                .map(|(l, k, v)| (*l, k.as_str(), v.as_str()))
+                .collect::<Vec<_>>()
+        );
+        assert!(parse("a = \\u12").is_err());
+        assert!(parse("a = \\ud83d").is_err());
+    }
+
+    #[test]
+    fn properties_test() {
+        let dir =
+            std::env::temp_dir().join(format!("salak_properties_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("app.properties"),
+            "# servers\nservers.0.host = a\nservers[1].host = b\nname = salak\nport = 80\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("app-dev.properties"), "port = 8080\n").unwrap();
+        let env = Salak::builder()
+            .set("salak.app.dir", dir.to_str().unwrap())
+            .set("salak.app.profile", "dev")
+            .build()
+            .unwrap();
+        assert_eq!("salak", env.require::<String>("name").unwrap());
+        assert_eq!(8080, env.require::<u16>("port").unwrap());
+        assert_eq!("a", env.require::<String>("servers[0].host").unwrap());
+        assert_eq!("b", env.require::<String>("servers[1].host").unwrap());
+
+        let source = Properties::new(FileItem(dir.join("app.properties"))).unwrap();
+        assert_eq!(
+            Some(format!("{}:5", dir.join("app.properties").display())),
+            source.location(&Key::from_str("port"))
+        );
+    }
+}
@@ -10,16 +10,16 @@ impl PropertySource for Random {
     #[inline]
     fn get_property(&self, key: &Key<'_>) -> Option<Property<'_>> {
         match key.as_str() {
-            "random.u8" => Some(Property::I(rand::random::<u8>() as i64)),
-            "random.u16" => Some(Property::I(rand::random::<u16>() as i64)),
-            "random.u32" => Some(Property::I(rand::random::<u32>() as i64)),
-            "random.u64" => Some(Property::O(rand::random::<u64>().to_string())),
+            "random.u8" => Some(Property::I(rand::random::<u8>().into())),
+            "random.u16" => Some(Property::I(rand::random::<u16>().into())),
+            "random.u32" => Some(Property::I(rand::random::<u32>().into())),
+            "random.u64" => Some(Property::I(rand::random::<u64>().into())),
             "random.u128" => Some(Property::O(rand::random::<u128>().to_string())),
-            "random.i8" => Some(Property::I(rand::random::<i8>() as i64)),
-            "random.i16" => Some(Property::I(rand::random::<i16>() as i64)),
-            "random.i32" => Some(Property::I(rand::random::<i32>() as i64)),
-            "random.i64" => Some(Property::I(rand::random::<i64>())),
-            "random.i128" => Some(Property::O(rand::random::<i128>().to_string())),
+            "random.i8" => Some(Property::I(rand::random::<i8>().into())),
+            "random.i16" => Some(Property::I(rand::random::<i16>().into())),
+            "random.i32" => Some(Property::I(rand::random::<i32>().into())),
+            "random.i64" => Some(Property::I(rand::random::<i64>().into())),
+            "random.i128" => Some(Property::I(rand::random::<i128>())),
             "random.usize" => Some(Property::O(rand::random::<usize>().to_string())),
             "random.isize" => Some(Property::O(rand::random::<isize>().to_string())),
             _ => None,
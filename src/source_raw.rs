@@ -1,35 +1,40 @@
-use core::ops::Deref;
+use core::ops::{Deref, DerefMut};
 use parking_lot::Mutex;
-use std::{collections::HashSet, path::PathBuf, vec};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    error::Error,
+    path::PathBuf,
+    sync::Arc,
+    time::{Instant, SystemTime},
+    vec,
+};
 
 use crate::{
-    wrapper::IORef, FromEnvironment, IORefT, IsProperty, Key, Property, PropertyError,
-    PropertySource, SalakContext, SubKey, SubKeys, PREFIX,
+    err::LocatedError, wrapper::IORef, FromEnvironment, IORefT, IsProperty, Key, Property,
+    PropertyError, PropertySource, SalakContext, SubKey, SubKeys, PREFIX,
 };
 #[cfg(feature = "derive")]
 #[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
 use crate::{DescFromEnvironment, KeyDesc, PrefixedFromEnvironment, SalakDescContext};
 use crate::{Res, Void};
 
-enum PS<'a> {
-    Ref(&'a Box<dyn PropertySource>),
-    Own(Box<dyn PropertySource>),
-}
-
-impl Deref for PS<'_> {
-    type Target = dyn PropertySource;
-
-    fn deref(&self) -> &Self::Target {
-        match self {
-            PS::Own(f) => f.as_ref(),
-            PS::Ref(f) => f.as_ref(),
-        }
-    }
-}
+/// Last reload time of providers, and the source reloaded last time, which is used until
+/// the provider reloads again.
+type ReloadPolls = HashMap<usize, (Instant, Option<Arc<dyn PropertySource>>)>;
 
 pub(crate) struct PropertyRegistryInternal<'a> {
     name: &'a str,
-    providers: Vec<PS<'a>>,
+    providers: Vec<Arc<dyn PropertySource>>,
+    polls: Mutex<ReloadPolls>,
+    /// Attach locations of properties to parse errors.
+    pub(crate) locate: bool,
+    /// Max depth of placeholders referring to other keys.
+    pub(crate) max_depth: usize,
+    /// Parse [`Vec`] up to the max index, instead of stopping at the first missing index.
+    pub(crate) sparse_vec: bool,
+    /// Names of all registered sources in order, and whether they are empty.
+    #[cfg(feature = "log")]
+    registered: Vec<(String, bool)>,
 }
 
 impl PropertySource for PropertyRegistryInternal<'_> {
@@ -42,6 +47,10 @@ impl PropertySource for PropertyRegistryInternal<'_> {
         self.providers.iter().find_map(|p| p.get_property(key))
     }
 
+    fn contains(&self, key: &Key<'_>) -> bool {
+        self.providers.iter().any(|p| p.contains(key))
+    }
+
     fn is_empty(&self) -> bool {
         self.providers.is_empty() || self.providers.iter().all(|f| f.is_empty())
     }
@@ -51,14 +60,27 @@ impl PropertySource for PropertyRegistryInternal<'_> {
             .iter()
             .for_each(|f| f.get_sub_keys(key, sub_keys));
     }
+
+    fn location(&self, key: &Key<'_>) -> Option<String> {
+        self.providers
+            .iter()
+            .find(|p| p.get_property(key).is_some())
+            .and_then(|p| p.location(key))
+    }
 }
 
 impl<'a> PropertyRegistryInternal<'a> {
     pub(crate) fn register_by_ref(&mut self, provider: Box<dyn PropertySource>) {
+        #[cfg(feature = "log")]
+        self.registered
+            .push((provider.name().to_string(), provider.is_empty()));
         if !provider.is_empty() {
             #[cfg(feature = "log")]
             log::info!("Register source {}.", provider.name());
-            self.providers.push(PS::Own(provider));
+            self.providers.push(Arc::from(provider));
+        } else {
+            #[cfg(feature = "log")]
+            log::debug!("Source {} is empty, pruned.", provider.name());
         }
     }
 
@@ -74,6 +96,41 @@ impl<'a> PropertyRegistryInternal<'a> {
         Self {
             name,
             providers: vec![],
+            polls: Mutex::new(HashMap::new()),
+            locate: false,
+            max_depth: 32,
+            sparse_vec: false,
+            #[cfg(feature = "log")]
+            registered: vec![],
+        }
+    }
+
+    /// Log all registered sources in order of precedence, empty sources are ignored.
+    #[cfg(feature = "log")]
+    pub(crate) fn log_sources(&self) {
+        let sources: Vec<String> = self
+            .registered
+            .iter()
+            .enumerate()
+            .map(|(i, (name, empty))| {
+                if *empty {
+                    format!("{}. {} (empty)", i + 1, name)
+                } else {
+                    format!("{}. {}", i + 1, name)
+                }
+            })
+            .collect();
+        log::debug!("Sources in order of precedence: {}.", sources.join(", "));
+    }
+
+    /// Attach location of `key` to parse error if enabled.
+    fn locate(&self, key: &Key<'_>, err: Box<dyn Error>) -> Box<dyn Error> {
+        if !self.locate {
+            return err;
+        }
+        match self.location(key) {
+            Some(location) => Box::new(LocatedError::new(location, err)),
+            _ => err,
         }
     }
 
@@ -81,9 +138,36 @@ impl<'a> PropertyRegistryInternal<'a> {
         &'a self,
         key: &mut Key<'_>,
         def: Option<Property<'a>>,
+    ) -> Result<Option<Property<'a>>, PropertyError> {
+        self.resolve_property(key, self.get_property(key).or(def), 0)
+    }
+
+    /// Get property and trim its string value, whitespace only value is treated as
+    /// not found and `def` is used instead.
+    fn get_trim(
+        &'a self,
+        key: &mut Key<'_>,
+        def: Option<Property<'a>>,
+    ) -> Result<Option<Property<'a>>, PropertyError> {
+        match self.get(key, None)?.and_then(trim_property) {
+            Some(v) => Ok(Some(v)),
+            _ => Ok(self.resolve_property(key, def, 0)?.and_then(trim_property)),
+        }
+    }
+
+    fn resolve_property(
+        &'a self,
+        key: &mut Key<'_>,
+        val: Option<Property<'a>>,
+        depth: usize,
     ) -> Result<Option<Property<'a>>, PropertyError> {
         let tmp;
-        let v = match self.get_property(key).or(def) {
+        let v = match val {
+            // Values without placeholders or escapes are returned as is,
+            // so borrowed strings are not copied on every lookup.
+            Some(v @ Property::S(_)) | Some(v @ Property::O(_)) if !Self::need_resolve(&v) => {
+                return Ok(Some(v))
+            }
             Some(Property::S(v)) => v,
             Some(Property::O(v)) => {
                 tmp = v;
@@ -93,7 +177,17 @@ impl<'a> PropertyRegistryInternal<'a> {
         };
         let mut history = HashSet::new();
         history.insert(key.as_str().to_string());
-        Ok(Some(self.resolve(key, v, &mut history)?))
+        Ok(Some(self.resolve(key, v, &mut history, depth)?))
+    }
+
+    #[inline]
+    fn need_resolve(v: &Property<'_>) -> bool {
+        let pat: &[_] = &['$', '\\', '}'];
+        match v {
+            Property::S(v) => v.contains(pat),
+            Property::O(v) => v.contains(pat),
+            _ => false,
+        }
     }
 
     #[inline]
@@ -113,6 +207,7 @@ impl<'a> PropertyRegistryInternal<'a> {
         key: &Key<'_>,
         mut val: &str,
         history: &mut HashSet<String>,
+        depth: usize,
     ) -> Result<Property<'_>, PropertyError> {
         let mut stack = vec!["".to_owned()];
         let pat: &[_] = &['$', '\\', '}'];
@@ -143,14 +238,18 @@ impl<'a> PropertyRegistryInternal<'a> {
                 "}" => {
                     let last = stack.pop();
                     let v = Self::merge(last, &val[..pos]);
+                    // Keys never contain `:`, so the first one separates key and default,
+                    // and the default may contain more, such as `${url:http://host:80}`.
                     let (key, def) = match v.find(':') {
                         Some(pos) => (&v[..pos], Some(&v[pos + 1..])),
                         _ => (&v[..], None),
                     };
-                    if !history.insert(key.to_string()) {
+                    if depth >= self.max_depth || !history.insert(key.to_string()) {
                         return Err(PropertyError::RecursiveFail(key.to_owned()));
                     }
-                    let v = if let Some(p) = self.get(&mut Key::from_str(key), None)? {
+                    let mut k = Key::from_str(key);
+                    let p = self.get_property(&k);
+                    let v = if let Some(p) = self.resolve_property(&mut k, p, depth + 1)? {
                         String::from_property(p)?
                     } else if let Some(d) = def {
                         d.to_owned()
@@ -174,32 +273,195 @@ impl<'a> PropertyRegistryInternal<'a> {
         Err(PropertyError::ResolveFail(key.as_str().to_string()))
     }
 
-    pub(crate) fn reload(&self, iorefs: &'a Mutex<Vec<Box<dyn IORefT + Send>>>) -> Res<bool> {
-        let mut flag = false;
-        let registry = PropertyRegistryInternal {
-            name: "reload",
-            providers: self
-                .providers
-                .iter()
-                .map(|f| match f.reload_source() {
-                    Ok(None) => Ok(match f {
-                        PS::Own(v) => PS::Ref(&*v),
-                        PS::Ref(v) => PS::Ref(*v),
-                    }),
-                    Ok(Some(v)) => {
-                        flag = true;
-                        Ok(PS::Own(v))
+    #[cfg(feature = "derive")]
+    pub(crate) fn resolve_placeholder(&self, val: &str) -> Res<String> {
+        String::from_property(self.resolve(&Key::new(), val, &mut HashSet::new(), 0)?)
+    }
+
+    /// Resolved properties under `prefix`, keys failing to resolve are omitted.
+    pub(crate) fn snapshot(&'a self, prefix: &str) -> BTreeMap<String, String> {
+        let mut keys = vec![];
+        self.keys(&mut Key::from_str(prefix), &mut keys);
+        keys.into_iter()
+            .filter_map(|k| {
+                let v = self.get(&mut Key::from_str(&k), None).ok()??;
+                String::from_property(v).ok().map(|v| (k, v))
+            })
+            .collect()
+    }
+
+    /// Enumerate all keys holding a property under `key`, including nested ones.
+    pub(crate) fn keys<'b>(&'b self, key: &mut Key<'b>, keys: &mut Vec<String>) {
+        if self.get_property(key).is_some() {
+            keys.push(key.as_str().to_string());
+        }
+        let mut sub_keys = SubKeys::new();
+        self.get_sub_keys(key, &mut sub_keys);
+        let mut subs: Vec<SubKey<'_>> = sub_keys.str_keys().into_iter().map(SubKey::S).collect();
+        subs.sort_unstable_by_key(|k| match k {
+            SubKey::S(v) => *v,
+            _ => "",
+        });
+        if let Some(max) = sub_keys.max() {
+            subs.extend((0..=max).map(SubKey::I));
+        }
+        for sub_key in subs {
+            key.push(sub_key);
+            self.keys(key, keys);
+            key.pop();
+        }
+    }
+
+    /// Get structured value under `key` directly, if only one source provides properties
+    /// under `key` and no value needs resolving, otherwise properties need to be merged.
+    #[cfg(feature = "json")]
+    fn structured_json(&self, key: &Key<'_>) -> Option<&serde_json::Value> {
+        use serde_json::Value;
+        fn need_resolve(v: &Value) -> bool {
+            match v {
+                Value::String(v) => v.contains(['$', '\\', '}']),
+                Value::Array(vs) => vs.iter().any(need_resolve),
+                Value::Object(vs) => vs.values().any(need_resolve),
+                _ => false,
+            }
+        }
+        let mut found = self.providers.iter().filter(|p| {
+            let mut sub_keys = SubKeys::new();
+            p.get_sub_keys(key, &mut sub_keys);
+            p.get_property(key).is_some() || !sub_keys.is_empty()
+        });
+        let provider = found.next()?;
+        if found.next().is_some() {
+            return None;
+        }
+        provider.get_json(key).filter(|v| !need_resolve(v))
+    }
+
+    /// Reconstruct properties under `key` as json value.
+    #[cfg(feature = "json")]
+    pub(crate) fn json_value(&'a self, key: &mut Key<'a>) -> Res<Option<serde_json::Value>> {
+        use serde_json::{Map, Number, Value};
+        use std::convert::TryFrom;
+        if let Some(v) = self.structured_json(key) {
+            return Ok(Some(v.clone()));
+        }
+        let mut sub_keys = SubKeys::new();
+        self.get_sub_keys(key, &mut sub_keys);
+        if let Some(max) = sub_keys.max() {
+            let mut vs = Vec::with_capacity(max + 1);
+            for i in 0..=max {
+                key.push(SubKey::I(i));
+                let v = self.json_value(key);
+                key.pop();
+                vs.push(v?.unwrap_or(Value::Null));
+            }
+            return Ok(Some(Value::Array(vs)));
+        }
+        let str_keys = sub_keys.str_keys();
+        if !str_keys.is_empty() {
+            let mut map = Map::new();
+            for k in str_keys {
+                key.push(SubKey::S(k));
+                let v = self.json_value(key);
+                key.pop();
+                if let Some(v) = v? {
+                    map.insert(k.to_string(), v);
+                }
+            }
+            return Ok(Some(Value::Object(map)));
+        }
+        Ok(match self.get(key, None)? {
+            Some(Property::S(v)) => Some(Value::String(v.to_string())),
+            Some(Property::O(v)) => Some(Value::String(v)),
+            Some(Property::I(v)) => Some(if let Ok(v) = i64::try_from(v) {
+                Value::Number(v.into())
+            } else if let Ok(v) = u64::try_from(v) {
+                Value::Number(v.into())
+            } else {
+                Value::String(v.to_string())
+            }),
+            Some(Property::F(v)) => Some(Number::from_f64(v).map_or(Value::Null, Value::Number)),
+            Some(Property::B(v)) => Some(Value::Bool(v)),
+            None => None,
+        })
+    }
+
+    /// Registry sharing sources currently in use, sources reloaded last time are used
+    /// instead of the registered ones.
+    #[cfg(feature = "app")]
+    pub(crate) fn current(&self) -> PropertyRegistryInternal<'a> {
+        let polls = self.polls.lock();
+        let providers = self
+            .providers
+            .iter()
+            .enumerate()
+            .map(|(i, f)| match polls.get(&i) {
+                Some((_, Some(v))) => v.clone(),
+                _ => f.clone(),
+            })
+            .collect();
+        drop(polls);
+        self.with_providers(self.name, providers)
+    }
+
+    fn with_providers(
+        &self,
+        name: &'a str,
+        providers: Vec<Arc<dyn PropertySource>>,
+    ) -> PropertyRegistryInternal<'a> {
+        PropertyRegistryInternal {
+            name,
+            providers,
+            polls: Mutex::new(HashMap::new()),
+            locate: self.locate,
+            max_depth: self.max_depth,
+            sparse_vec: self.sparse_vec,
+            #[cfg(feature = "log")]
+            registered: vec![],
+        }
+    }
+
+    /// Reload sources, then update values tracked by `iorefs` and call `post_reload` with
+    /// the reloaded registry. Names of reloaded sources are returned.
+    pub(crate) fn reload(
+        &self,
+        iorefs: &'a Mutex<Vec<Box<dyn IORefT + Send>>>,
+        post_reload: impl FnOnce(&PropertyRegistryInternal<'_>) -> Void,
+    ) -> Res<Vec<String>> {
+        let mut reloaded = vec![];
+        let mut polls = self.polls.lock();
+        let providers = self
+            .providers
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let interval = f.reload_interval();
+                if let (Some(interval), Some((last, v))) = (interval, polls.get(&i)) {
+                    if last.elapsed() < interval {
+                        return Ok(v.clone().unwrap_or_else(|| f.clone()));
                     }
-                    Err(err) => Err(err),
-                })
-                .collect::<Result<Vec<PS<'_>>, PropertyError>>()?,
-        };
+                }
+                let v = f.reload_source()?.map(Arc::from);
+                let poll = polls.entry(i).or_insert_with(|| (Instant::now(), None));
+                poll.0 = Instant::now();
+                if v.is_some() {
+                    reloaded.push(f.name().to_string());
+                    poll.1 = v;
+                }
+                Ok(poll.1.clone().unwrap_or_else(|| f.clone()))
+            })
+            .collect::<Result<Vec<_>, PropertyError>>()?;
+        drop(polls);
+        let registry = self.with_providers("reload", providers);
 
-        let guard = iorefs.lock();
+        let mut guard = iorefs.lock();
         for io in guard.iter() {
             io.reload_ref(&registry, iorefs)?;
         }
-        Ok(flag)
+        guard.retain(|io| io.is_alive());
+        drop(guard);
+        (post_reload)(&registry)?;
+        Ok(reloaded)
     }
 
     #[inline]
@@ -208,8 +470,8 @@ impl<'a> PropertyRegistryInternal<'a> {
         sub_key: &str,
         iorefs: &'a Mutex<Vec<Box<dyn IORefT + Send>>>,
     ) -> Res<T> {
-        let mut key = Key::new();
-        SalakContext::new(&self, iorefs, &mut key).require_def(sub_key, None)
+        let mut key = Key::from_str(sub_key);
+        SalakContext::new(&self, iorefs, &mut key).require_def("", None)
     }
 }
 #[cfg(feature = "derive")]
@@ -266,46 +528,188 @@ impl<'a> SalakContext<'a> {
     #[inline]
     pub fn require_def<T: FromEnvironment>(
         &mut self,
-        sub_key: &'a str,
+        sub_key: &str,
         def: Option<Property<'_>>,
     ) -> Res<T> {
         self.require_def_internal(sub_key, def)
     }
 
     #[inline]
-    pub(crate) fn require_def_internal<T: FromEnvironment, K: Into<SubKey<'a>>>(
+    pub(crate) fn require_def_internal<'b, T: FromEnvironment, K: Into<SubKey<'b>>>(
+        &mut self,
+        sub_key: K,
+        def: Option<Property<'_>>,
+    ) -> Res<T> {
+        self.require_def_with(sub_key, def, false)
+    }
+
+    fn require_def_with<'b, T: FromEnvironment, K: Into<SubKey<'b>>>(
         &mut self,
         sub_key: K,
         def: Option<Property<'_>>,
+        trim: bool,
     ) -> Res<T> {
         let flag = self.into_sub_key(sub_key);
-        let val = match self.registry.get(self.key, def) {
-            Ok(val) => Ok(T::from_env(val, self)),
+        let val = if trim {
+            self.registry.get_trim(self.key, def)
+        } else {
+            self.registry.get(self.key, def)
+        };
+        let val = match val {
+            Ok(val) => match T::from_env(val, self) {
+                Err(PropertyError::ParseFail(None, v)) if !self.key.as_str().is_empty() => {
+                    Err(PropertyError::ParseFail(
+                        Some(self.key.as_str().to_string()),
+                        self.registry.locate(self.key, v),
+                    ))
+                }
+                val => val,
+            },
             Err(e) => Err(e),
         };
         if flag {
             self.key.pop();
         }
-        match val? {
-            Err(PropertyError::ParseFail(None, v)) if !self.key.as_str().is_empty() => Err(
-                PropertyError::ParseFail(Some(self.key.as_str().to_string()), v),
-            ),
-            val => val,
+        val
+    }
+
+    /// Log a warning if property with `sub_key` exists, used by
+    /// `#[salak(deprecated = "...")]` fields.
+    #[doc(hidden)]
+    #[allow(unused_variables)]
+    pub fn warn_deprecated(&mut self, sub_key: &'a str, msg: &str) {
+        #[cfg(feature = "log")]
+        {
+            let flag = self.into_sub_key(sub_key);
+            if self.exists() {
+                log::warn!("Key {} is deprecated, {}.", self.key.as_str(), msg);
+            }
+            if flag {
+                self.key.pop();
+            }
+        }
+    }
+
+    /// Check length of property with `sub_key`, used by `#[salak(min_len = ..)]`
+    /// and `#[salak(max_len = ..)]` fields.
+    #[doc(hidden)]
+    pub fn check_len(
+        &mut self,
+        sub_key: &'a str,
+        len: usize,
+        min: Option<usize>,
+        max: Option<usize>,
+    ) -> Void {
+        let msg = match (min, max) {
+            (Some(min), _) if len < min => format!("length {} is less than min_len {}", len, min),
+            (_, Some(max)) if len > max => {
+                format!("length {} is greater than max_len {}", len, max)
+            }
+            _ => return Ok(()),
+        };
+        let flag = self.into_sub_key(sub_key);
+        let key = self.key.as_str().to_string();
+        if flag {
+            self.key.pop();
+        }
+        Err(match PropertyError::parse_fail(&msg) {
+            PropertyError::ParseFail(_, e) => PropertyError::ParseFail(Some(key), e),
+            e => e,
+        })
+    }
+
+    /// Parse property from the first existing alias, if no alias exists,
+    /// then parse from `sub_key`. Used by `#[salak(alias = "...")]` fields.
+    #[doc(hidden)]
+    pub fn require_alias<T: FromEnvironment>(
+        &mut self,
+        aliases: &[&'a str],
+        sub_key: &'a str,
+        def: Option<Property<'_>>,
+    ) -> Res<T> {
+        self.require_alias_with(aliases, sub_key, def, false)
+    }
+
+    /// Parse property like [`SalakContext::require_alias`], and trim string value
+    /// before parsing, whitespace only value is treated as not found.
+    /// Used by `#[salak(trim)]` fields.
+    #[doc(hidden)]
+    pub fn require_trim<T: FromEnvironment>(
+        &mut self,
+        aliases: &[&'a str],
+        sub_key: &'a str,
+        def: Option<Property<'_>>,
+    ) -> Res<T> {
+        self.require_alias_with(aliases, sub_key, def, true)
+    }
+
+    fn require_alias_with<T: FromEnvironment>(
+        &mut self,
+        aliases: &[&'a str],
+        sub_key: &'a str,
+        def: Option<Property<'_>>,
+        trim: bool,
+    ) -> Res<T> {
+        for alias in aliases {
+            let mut path = SubKey::parse_path(alias);
+            let last = match path.pop() {
+                Some(last) => last,
+                _ => continue,
+            };
+            let size = path.len();
+            for k in path {
+                self.key.push(k);
+            }
+            self.key.push(last);
+            let found = self.exists();
+            self.key.pop();
+            let val = if found {
+                Some(self.require_def_with::<T, SubKey<'a>>(last, None, trim))
+            } else {
+                None
+            };
+            for _ in 0..size {
+                self.key.pop();
+            }
+            if let Some(val) = val {
+                return val;
+            }
         }
+        self.require_def_with(sub_key, def, trim)
+    }
+
+    /// Push `sub_key` to current key, for manually parsing nested properties.
+    /// The key is popped when the returned [`KeyGuard`] drops, so pushes and pops
+    /// are always balanced.
+    pub fn push_key(&mut self, sub_key: &str) -> KeyGuard<'_, 'a> {
+        let flag = self.into_sub_key(sub_key);
+        KeyGuard(self, flag)
+    }
+
+    /// Push `index` to current key, see [`SalakContext::push_key`].
+    pub fn push_index(&mut self, index: usize) -> KeyGuard<'_, 'a> {
+        let flag = self.into_sub_key(index);
+        KeyGuard(self, flag)
+    }
+
+    fn exists(&mut self) -> bool {
+        self.registry.get_property(self.key).is_some() || !self.get_sub_keys().is_empty()
     }
 
-    pub(crate) fn get_sub_keys(&mut self) -> SubKeys<'a> {
+    /// Get sub keys of current key.
+    pub fn get_sub_keys(&mut self) -> SubKeys<'a> {
         let mut sub_keys = SubKeys::new();
         self.registry.get_sub_keys(&mut self.key, &mut sub_keys);
         sub_keys
     }
 
+    /// Get current key.
     #[inline]
-    pub(crate) fn current_key(&self) -> &str {
+    pub fn current_key(&self) -> &str {
         self.key.as_str()
     }
 
-    fn into_sub_key<K: Into<SubKey<'a>>>(&mut self, k: K) -> bool {
+    fn into_sub_key<'b, K: Into<SubKey<'b>>>(&mut self, k: K) -> bool {
         let v = k.into();
         let flag = !v.is_empty();
         if flag {
@@ -337,6 +741,33 @@ impl<'a> SalakContext<'a> {
     }
 }
 
+/// Split `val` of `key` by `delimiter`, and parse each trimmed element as `T` under key
+/// such as `key[1]`. Empty elements are missing values, so `Option<T>` gets `None` for them,
+/// while other types fail with [`PropertyError::NotFound`].
+pub(crate) fn parse_delimited<T: FromEnvironment>(
+    key: &str,
+    val: &str,
+    delimiter: char,
+) -> Res<Vec<T>> {
+    if val.is_empty() {
+        return Ok(vec![]);
+    }
+    let registry = PropertyRegistryInternal::new("delimited");
+    let iorefs = Mutex::new(vec![]);
+    val.split(delimiter)
+        .enumerate()
+        .map(|(i, v)| {
+            let mut key = Key::from_str(key);
+            key.push(SubKey::I(i));
+            let v = match v.trim() {
+                "" => None,
+                v => Some(Property::S(v)),
+            };
+            T::from_env(v, &mut SalakContext::new(&registry, &iorefs, &mut key))
+        })
+        .collect()
+}
+
 impl<T: FromEnvironment> FromEnvironment for Option<T> {
     fn from_env(val: Option<Property<'_>>, env: &mut SalakContext<'_>) -> Res<Self> {
         match T::from_env(val, env) {
@@ -347,6 +778,27 @@ impl<T: FromEnvironment> FromEnvironment for Option<T> {
     }
 }
 
+/// Properties under key as json value, subtree is consumed directly from structured
+/// source if possible, see [`PropertySource::get_json()`].
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+impl FromEnvironment for serde_json::Value {
+    fn from_env(_: Option<Property<'_>>, env: &mut SalakContext<'_>) -> Res<Self> {
+        match env.registry.json_value(env.key)? {
+            Some(v) => Ok(v),
+            _ => Err(PropertyError::NotFound(env.current_key().to_string())),
+        }
+    }
+}
+
+#[cfg(all(feature = "json", feature = "derive"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "json", feature = "derive"))))]
+impl DescFromEnvironment for serde_json::Value {
+    fn key_desc(env: &mut SalakDescContext<'_>) {
+        env.leaf_desc();
+    }
+}
+
 #[cfg(feature = "derive")]
 #[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
 impl<T: DescFromEnvironment> DescFromEnvironment for Option<T> {
@@ -356,9 +808,38 @@ impl<T: DescFromEnvironment> DescFromEnvironment for Option<T> {
     }
 }
 
+macro_rules! impl_pointer {
+    ($($x:ident)+) => {$(
+        impl<T: FromEnvironment> FromEnvironment for $x<T> {
+            #[inline]
+            fn from_env(val: Option<Property<'_>>, env: &mut SalakContext<'_>) -> Res<Self> {
+                Ok($x::new(T::from_env(val, env)?))
+            }
+        }
+
+        #[cfg(feature = "derive")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+        impl<T: DescFromEnvironment> DescFromEnvironment for $x<T> {
+            #[inline]
+            fn key_desc(env: &mut SalakDescContext<'_>) {
+                T::key_desc(env);
+            }
+        }
+    )+};
+}
+
+// `Box` is a fundamental type, so a generic impl for it would overlap with the
+// blanket impls over `IsProperty` and `EnumProperty`.
+mod pointer {
+    use crate::*;
+    use std::{rc::Rc, sync::Arc};
+    impl_pointer!(Rc Arc);
+}
+
 pub(crate) struct FileConfig {
     dir: Option<String>,
     name: String,
+    names: HashMap<String, String>,
     profile: String,
     env_profile: PropertyRegistryInternal<'static>,
     env_default: PropertyRegistryInternal<'static>,
@@ -369,6 +850,7 @@ impl FromEnvironment for FileConfig {
         Ok(FileConfig {
             dir: env.require_def("dir", None)?,
             name: env.require_def("filename", Some(Property::S("app")))?,
+            names: env.require_def("filenames", None)?,
             profile: env.require_def("profile", Some(Property::S("default")))?,
             env_profile: PropertyRegistryInternal::new("profile-files"),
             env_default: PropertyRegistryInternal::new("default-files"),
@@ -382,6 +864,7 @@ impl DescFromEnvironment for FileConfig {
     fn key_desc(env: &mut SalakDescContext<'_>) {
         env.add_key_desc::<Option<String>>("dir", None, None, None);
         env.add_key_desc::<String>("filename", Some(false), Some("app"), None);
+        env.add_key_desc::<HashMap<String, String>>("filenames", None, None, None);
         env.add_key_desc::<String>("profile", Some(false), Some("default"), None);
     }
 }
@@ -412,13 +895,69 @@ impl FileConfig {
     pub(crate) fn build<F: Fn(FileItem) -> Res<S>, S: PropertySource + 'static>(
         &mut self,
         ext: &str,
+        deny_empty: bool,
         f: F,
     ) -> Void {
+        fn includes(source: &dyn PropertySource) -> Res<Vec<String>> {
+            let key = Key::from_str(INCLUDE);
+            let mut sub_keys = SubKeys::new();
+            source.get_sub_keys(&key, &mut sub_keys);
+            let mut v = vec![];
+            if let Some(p) = source.get_property(&key) {
+                v.push(String::from_property(p)?);
+            }
+            if let Some(max) = sub_keys.max() {
+                for i in 0..=max {
+                    let key = format!("{}[{}]", INCLUDE, i);
+                    if let Some(p) = source.get_property(&Key::from_str(&key)) {
+                        v.push(String::from_property(p)?);
+                    }
+                }
+            }
+            Ok(v)
+        }
+
+        fn load<F: Fn(FileItem) -> Res<S>, S: PropertySource + 'static>(
+            f: &F,
+            path: PathBuf,
+            env: &mut PropertyRegistryInternal<'_>,
+            chain: &mut Vec<PathBuf>,
+            deny_empty: bool,
+        ) -> Void {
+            let canonical = path
+                .canonicalize()
+                .map_err(|e| FileItem(path.clone()).io_error(e))?;
+            if chain.contains(&canonical) {
+                return Err(PropertyError::parse_fail(&format!(
+                    "include cycle at {}",
+                    path.display()
+                )));
+            }
+            let source = (f)(FileItem(path.clone()))?;
+            if deny_empty && source.is_empty() {
+                return Err(PropertyError::parse_fail(&format!(
+                    "empty file {}",
+                    path.display()
+                )));
+            }
+            let files = includes(&source)?;
+            env.register_by_ref(Box::new(source));
+            chain.push(canonical);
+            for file in files {
+                let mut include = path.parent().map(PathBuf::from).unwrap_or_default();
+                include.push(file);
+                load(f, include, env, chain, deny_empty)?;
+            }
+            chain.pop();
+            Ok(())
+        }
+
         fn make<F: Fn(FileItem) -> Res<S>, S: PropertySource + 'static>(
             f: F,
             file: String,
             dir: &Option<String>,
             env: &mut PropertyRegistryInternal<'_>,
+            deny_empty: bool,
         ) -> Void {
             let mut path = PathBuf::new();
             if let Some(d) = &dir {
@@ -426,43 +965,145 @@ impl FileConfig {
             }
             path.push(file);
             if path.exists() {
-                env.register_by_ref(Box::new((f)(FileItem(path))?));
+                load(&f, path, env, &mut vec![], deny_empty)?;
             }
             Ok(())
         }
 
+        let name = self.names.get(ext).unwrap_or(&self.name);
         make(
             &f,
-            format!("{}-{}.{}", self.name, self.profile, ext),
+            format!("{}-{}.{}", name, self.profile, ext),
             &self.dir,
             &mut self.env_profile,
+            deny_empty,
         )?;
         make(
             &f,
-            format!("{}.{}", self.name, ext),
+            format!("{}.{}", name, ext),
             &self.dir,
             &mut self.env_default,
+            deny_empty,
         )
     }
 }
 
+/// Trim string property, whitespace only value is treated as not found.
+fn trim_property(p: Property<'_>) -> Option<Property<'_>> {
+    match p {
+        Property::S(v) => Some(v.trim()).filter(|v| !v.is_empty()).map(Property::S),
+        Property::O(v) => match v.trim() {
+            "" => None,
+            t if t.len() == v.len() => Some(Property::O(v)),
+            t => Some(Property::O(t.to_string())),
+        },
+        v => Some(v),
+    }
+}
+
+/// Guard of key pushed by [`SalakContext::push_key`] or [`SalakContext::push_index`],
+/// which pops the key when dropped.
+#[allow(missing_debug_implementations)]
+pub struct KeyGuard<'b, 'a>(&'b mut SalakContext<'a>, bool);
+
+impl KeyGuard<'_, '_> {
+    /// Pop the pushed key.
+    #[inline]
+    pub fn pop_key(self) {}
+}
+
+impl<'a> Deref for KeyGuard<'_, 'a> {
+    type Target = SalakContext<'a>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl DerefMut for KeyGuard<'_, '_> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0
+    }
+}
+
+impl Drop for KeyGuard<'_, '_> {
+    fn drop(&mut self) {
+        if self.1 {
+            self.0.key.pop();
+        }
+    }
+}
+
+/// Key of files included by a configuration file, relative to the including file.
+const INCLUDE: &str = "salak.include";
+
 #[derive(Debug, Clone)]
-pub(crate) struct FileItem(PathBuf);
+pub(crate) struct FileItem(pub(crate) PathBuf);
+
+/// Modification time and size of a file.
+type Stamp = (SystemTime, u64);
 
 #[allow(dead_code)]
 impl FileItem {
     pub(crate) fn load(&self) -> Res<String> {
-        Ok(std::fs::read_to_string(self.0.clone())?)
+        std::fs::read_to_string(&self.0).map_err(|e| self.io_error(e))
+    }
+
+    /// Attach path of current file to io error, the error kind is kept.
+    pub(crate) fn io_error(&self, err: std::io::Error) -> PropertyError {
+        std::io::Error::new(err.kind(), format!("{}: {}", self.name(), err)).into()
     }
 
     pub(crate) fn name(&self) -> String {
         self.0.as_path().display().to_string()
     }
+
+    /// Modification time and size of the file, used to skip reloading unchanged files.
+    pub(crate) fn stamp(&self) -> Option<Stamp> {
+        let meta = std::fs::metadata(&self.0).ok()?;
+        Some((meta.modified().ok()?, meta.len()))
+    }
+}
+
+/// Stamp of the file last loaded by a file source, which is updated in place when reloading,
+/// because the registered source is never replaced. Unchanged files are not parsed again, and
+/// the registry keeps using the source reloaded last time.
+#[cfg(any(feature = "toml", feature = "yaml", feature = "properties"))]
+#[derive(Debug)]
+pub(crate) struct FileStamp(Mutex<Option<Stamp>>);
+
+#[cfg(any(feature = "toml", feature = "yaml", feature = "properties"))]
+impl FileStamp {
+    pub(crate) fn new(item: &FileItem) -> Self {
+        Self(Mutex::new(item.stamp()))
+    }
+
+    /// Reload source by `load` if the file changed since it was loaded last time.
+    pub(crate) fn reload<S: PropertySource + 'static>(
+        &self,
+        item: &FileItem,
+        load: impl FnOnce(FileItem) -> Res<S>,
+    ) -> Res<Option<Box<dyn PropertySource>>> {
+        let mut last = self.0.lock();
+        let stamp = item.stamp();
+        if stamp.is_some() && *last == stamp {
+            return Ok(None);
+        }
+        let source = (load)(item.clone())?;
+        *last = stamp;
+        Ok(Some(Box::new(source)))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use raw_ioref::IORef;
+    use std::{
+        sync::atomic::{AtomicU64, Ordering},
+        time::Duration,
+    };
 
     use crate::{
         source::{Key, SubKeys},
@@ -477,7 +1118,7 @@ mod tests {
         }
 
         fn get_property(&self, _: &Key<'_>) -> Option<Property<'_>> {
-            Some(Property::I(self.0 as i64))
+            Some(Property::I(self.0.into()))
         }
 
         fn get_sub_keys<'a>(&'a self, _: &Key<'_>, _: &mut SubKeys<'a>) {}
@@ -490,6 +1131,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn file_load_error_test() {
+        let path = std::env::temp_dir().join("salak_not_exists.toml");
+        let err: std::io::Error = source_raw::FileItem(path.clone())
+            .load()
+            .unwrap_err()
+            .into();
+        assert_eq!(std::io::ErrorKind::NotFound, err.kind());
+        assert!(err.to_string().starts_with(&path.display().to_string()));
+    }
+
     #[test]
     fn reload_test() {
         let mut env = Salak::new().unwrap();
@@ -500,5 +1152,464 @@ mod tests {
         assert_eq!(1, u8ref.get_val().unwrap());
         env.reload().unwrap();
         assert_eq!(1, u8ref.get_val().unwrap());
+        // Properties are not snapshotted without reports.
+        assert!(env.tracked.lock().is_empty());
+    }
+
+    #[test]
+    fn freeze_test() {
+        let mut env = Salak::new().unwrap();
+        env.register(Reload(0));
+        let live = env.require::<IORef<u8>>("").unwrap();
+        let watcher = env.watch_key::<u8>("").unwrap();
+        assert!(env.reload().unwrap());
+        assert_eq!(1, live.get_val().unwrap());
+        assert_eq!(Ok(1), watcher.try_recv());
+
+        let mut env = Salak::new().unwrap();
+        env.register(Reload(0));
+        let frozen = env.require::<IORef<u8>>("").unwrap();
+        let watcher = env.watch_key::<u8>("").unwrap();
+        let env = env.freeze();
+        assert!(!env.reload().unwrap());
+        assert_eq!(0, frozen.get_val().unwrap());
+        assert_eq!(0, env.require::<u8>("").unwrap());
+        assert_eq!(
+            Err(std::sync::mpsc::TryRecvError::Disconnected),
+            watcher.try_recv()
+        );
+    }
+
+    #[test]
+    fn reload_detailed_test() {
+        let mut env = Salak::new().unwrap();
+        env.register(Reload(0));
+        let report = env.reload_detailed().unwrap();
+        assert!(report.changed.is_empty());
+        assert_eq!(vec!["reload".to_string()], report.sources);
+
+        // Source is reloaded from the registered one, so it is always `Reload(1)`.
+        let u8ref = env.require::<IORef<u8>>("a").unwrap();
+        let report = env.reload_detailed().unwrap();
+        assert_eq!(1, report.changed.len());
+        assert_eq!(
+            Some(&(Some("0".to_string()), Some("1".to_string()))),
+            report.changed.get("a")
+        );
+        assert_eq!(1, u8ref.get_val().unwrap());
+
+        let report = env.reload_detailed().unwrap();
+        assert!(report.changed.is_empty());
+        assert_eq!(1, report.sources.len());
+    }
+
+    #[test]
+    fn on_reload_test() {
+        use std::sync::Arc;
+        let mut env = Salak::new().unwrap();
+        env.register(Reload(0));
+        let first = Arc::new(AtomicU64::new(0));
+        let second = Arc::new(AtomicU64::new(0));
+        let f = first.clone();
+        env.on_reload(Box::new(move || {
+            f.fetch_add(1, Ordering::SeqCst);
+        }));
+        env.reload().unwrap();
+        let s = second.clone();
+        env.on_reload(Box::new(move || {
+            s.fetch_add(1, Ordering::SeqCst);
+        }));
+        env.reload().unwrap();
+        env.reload().unwrap();
+        assert_eq!(3, first.load(Ordering::SeqCst));
+        assert_eq!(2, second.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn on_reload_register_test() {
+        use std::sync::Arc;
+        let mut env = Salak::new().unwrap();
+        env.register(Reload(0));
+        let env = Arc::new(env);
+        let count = Arc::new(AtomicU64::new(0));
+        let (e, c) = (Arc::downgrade(&env), count.clone());
+        env.on_reload(Box::new(move || {
+            let c = c.clone();
+            if let Some(env) = e.upgrade() {
+                env.on_reload(Box::new(move || {
+                    c.fetch_add(1, Ordering::SeqCst);
+                }));
+            }
+        }));
+        env.reload().unwrap();
+        assert_eq!(0, count.load(Ordering::SeqCst));
+        env.reload().unwrap();
+        assert_eq!(1, count.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn require_ref_test() {
+        let env = Salak::builder()
+            .set("a", "salak")
+            .set("b", "1")
+            .build()
+            .unwrap();
+        let a = env.require_ref::<String>("a").unwrap();
+        assert_eq!("salak", a.as_str());
+        assert!(std::sync::Arc::ptr_eq(
+            &a,
+            &env.require_ref::<String>("a").unwrap()
+        ));
+        assert_eq!(1, *env.require_ref::<u8>("b").unwrap());
+        assert_eq!("1", env.require_ref::<String>("b").unwrap().as_str());
+        assert!(env.require_ref::<u8>("a").is_err());
+
+        env.reload().unwrap();
+        let reloaded = env.require_ref::<String>("a").unwrap();
+        assert_eq!(a, reloaded);
+        assert!(!std::sync::Arc::ptr_eq(&a, &reloaded));
+    }
+
+    static POLLS: AtomicU64 = AtomicU64::new(0);
+
+    struct Throttled(u64, Duration);
+
+    impl PropertySource for Throttled {
+        fn name(&self) -> &str {
+            "throttled"
+        }
+
+        fn get_property(&self, _: &Key<'_>) -> Option<Property<'_>> {
+            Some(Property::I(self.0.into()))
+        }
+
+        fn get_sub_keys<'a>(&'a self, _: &Key<'_>, _: &mut SubKeys<'a>) {}
+
+        fn is_empty(&self) -> bool {
+            false
+        }
+
+        fn reload_source(&self) -> Result<Option<Box<dyn PropertySource>>, PropertyError> {
+            let n = POLLS.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(Some(Box::new(Throttled(n, self.1))))
+        }
+
+        fn reload_interval(&self) -> Option<Duration> {
+            Some(self.1)
+        }
+    }
+
+    #[test]
+    fn reload_interval_test() {
+        let mut env = Salak::new().unwrap();
+        env.register(Throttled(0, Duration::from_secs(3600)));
+        let val = env.require::<IORef<u64>>("").unwrap();
+        assert_eq!(0, val.get_val().unwrap());
+        for _ in 0..3 {
+            env.reload().unwrap();
+            assert_eq!(1, POLLS.load(Ordering::SeqCst));
+            assert_eq!(1, val.get_val().unwrap());
+        }
+
+        let mut env = Salak::new().unwrap();
+        env.register(Throttled(0, Duration::from_secs(0)));
+        for i in 2..5 {
+            env.reload().unwrap();
+            assert_eq!(i, POLLS.load(Ordering::SeqCst));
+        }
+    }
+
+    #[test]
+    fn scoped_test() {
+        let env = Salak::builder()
+            .set("app.name", "salak")
+            .set("app.db.port", "5432")
+            .set("app.db.hosts[0]", "a")
+            .set("app.db.hosts[1]", "b")
+            .build()
+            .unwrap();
+        let app = env.scoped("app");
+        assert_eq!("salak", app.require::<String>("name").unwrap());
+        assert_eq!(None, app.require::<Option<String>>("port").unwrap());
+        let db = app.scoped("db");
+        assert_eq!("app.db", db.prefix());
+        assert_eq!(5432, db.require::<u16>("port").unwrap());
+        assert_eq!(vec!["a", "b"], db.require::<Vec<String>>("hosts").unwrap());
+        assert_eq!("b", db.scoped("hosts").require::<String>("[1]").unwrap());
+        assert_eq!(5432, env.scoped("").require::<u16>("app.db.port").unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn diff_test() {
+        let build = |env: &str, port: &str| {
+            let mut builder = Salak::builder()
+                .disable_system_env()
+                .configure_random(false)
+                .set("app.env", env)
+                .set("app.password", env)
+                .set("app.url", "http://${app.host:localhost}:${app.port}")
+                .set("app.port", port);
+            if env == "prod" {
+                builder = builder.set("app.host", "example.com");
+            } else {
+                builder = builder.set("app.debug", "true");
+            }
+            builder.build().unwrap()
+        };
+        let staging = build("staging", "8080");
+        let prod = build("prod", "8080");
+        let s = |v: &str| Some(v.to_string());
+        let diff = staging.diff_with(&prod, |k| k.ends_with("password"));
+        assert_eq!(
+            vec![
+                ("app.debug", (s("true"), None)),
+                ("app.env", (s("staging"), s("prod"))),
+                ("app.host", (None, s("example.com"))),
+                (
+                    "app.url",
+                    (s("http://localhost:8080"), s("http://example.com:8080"))
+                ),
+            ],
+            diff.iter()
+                .map(|(k, v)| (k.as_str(), v.clone()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(&(s("staging"), s("prod"))),
+            staging.diff(&prod).get("app.password")
+        );
+        assert!(prod.diff(&build("prod", "8080")).is_empty());
+    }
+
+    struct Counted(AtomicU64, AtomicU64);
+
+    impl PropertySource for Counted {
+        fn name(&self) -> &str {
+            "counted"
+        }
+
+        fn get_property(&self, key: &Key<'_>) -> Option<Property<'_>> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Some(Property::S("x")).filter(|_| key.as_str() == "a")
+        }
+
+        fn contains(&self, key: &Key<'_>) -> bool {
+            self.1.fetch_add(1, Ordering::SeqCst);
+            key.as_str() == "a"
+        }
+
+        fn get_sub_keys<'a>(&'a self, _: &Key<'_>, _: &mut SubKeys<'a>) {}
+
+        fn is_empty(&self) -> bool {
+            false
+        }
+    }
+
+    #[test]
+    fn contains_override_test() {
+        let source = std::sync::Arc::new(Counted(AtomicU64::new(0), AtomicU64::new(0)));
+        let mut env = Salak::builder().disable_system_env().build().unwrap();
+        env.register(
+            source
+                .clone()
+                .or(source::HashMapSource::new("map").set("b", "1")),
+        );
+        assert!(env.contains("a"));
+        assert!(env.contains("b"));
+        assert!(!env.contains("c"));
+        assert_eq!(0, source.0.load(Ordering::SeqCst));
+        assert_eq!(3, source.1.load(Ordering::SeqCst));
+        assert_eq!("x", env.require::<String>("a").unwrap());
+        assert_eq!(1, source.0.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn contains_test() {
+        let env = Salak::builder()
+            .set("a", "1")
+            .set("b", "${a}")
+            .set("c", "${missing}")
+            .set("d[0]", "x")
+            .build()
+            .unwrap();
+        for k in &["a", "b", "c", "d[0]"] {
+            assert!(env.contains(k));
+        }
+        assert!(env.require::<String>("c").is_err());
+        for k in &["missing", "d", "d[1]", "a.b"] {
+            assert!(!env.contains(k));
+        }
+    }
+
+    #[test]
+    fn has_any_test() {
+        let env = Salak::builder()
+            .set("redis.host", "localhost")
+            .set("redis.pool.max", "8")
+            .set("servers[0].host", "a")
+            .set("flag", "true")
+            .build()
+            .unwrap();
+        for k in &["redis", "redis.pool", "servers", "servers[0]", "flag"] {
+            assert!(env.has_any(k));
+        }
+        assert!(!env.contains("redis"));
+        for k in &["postgres", "redis.cache", "servers[1]", "flag.x", "redi"] {
+            assert!(!env.has_any(k));
+        }
+    }
+
+    #[test]
+    fn dump_effective_config_test() {
+        let mut env = Salak::builder()
+            .set("app.name", "salak")
+            .set("app.url", "http://${app.host}:${app.port}")
+            .set("app.password", "secret")
+            .set("app.servers[0]", "a")
+            .set("app.servers[1]", "b")
+            .build()
+            .unwrap();
+        env.register(
+            source::HashMapSource::new("default")
+                .set("app.name", "default")
+                .set("app.host", "localhost")
+                .set("app.port", "8080")
+                .set("appx", "x"),
+        );
+        let dump = env.dump_effective_config_with(|k| k.ends_with("password"));
+        let app: Vec<(&str, &str)> = dump
+            .iter()
+            .filter(|(k, _)| k.starts_with("app"))
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("app.host", "localhost"),
+                ("app.name", "salak"),
+                ("app.password", "******"),
+                ("app.port", "8080"),
+                ("app.servers[0]", "a"),
+                ("app.servers[1]", "b"),
+                ("app.url", "http://localhost:8080"),
+                ("appx", "x"),
+            ],
+            app
+        );
+        let map = env
+            .require::<std::collections::HashMap<String, String>>("app")
+            .unwrap();
+        assert_eq!(5, map.len());
+        assert_eq!(
+            Some("secret"),
+            env.dump_effective_config()
+                .get("app.password")
+                .map(|v| v.as_str())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn json_value_test() {
+        let env = Salak::builder()
+            .set("db.host", "localhost")
+            .set("db.url", "http://${db.host}")
+            .set_typed("db.port", 5432u16)
+            .set_typed("db.ssl", true)
+            .set("db.replicas[0].host", "r0")
+            .set("db.replicas[2].host", "r2")
+            .set("db.tags[0]", "a")
+            .set("db.tags[1]", "b")
+            .build()
+            .unwrap();
+        assert_eq!(
+            serde_json::json!({
+                "host": "localhost",
+                "url": "http://localhost",
+                "port": 5432,
+                "ssl": true,
+                "replicas": [{"host": "r0"}, null, {"host": "r2"}],
+                "tags": ["a", "b"],
+            }),
+            env.require_json_value("db").unwrap()
+        );
+        assert_eq!(
+            serde_json::json!("r0"),
+            env.require_json_value("db.replicas[0].host").unwrap()
+        );
+        assert_eq!(
+            serde_json::Value::Null,
+            env.require_json_value("not_exists").unwrap()
+        );
+    }
+
+    #[test]
+    fn disable_system_env_test() {
+        let env = Salak::new().unwrap();
+        assert_eq!(
+            std::env::var("PATH").ok(),
+            env.require::<Option<String>>("PATH").unwrap()
+        );
+        let env = Salak::builder().disable_system_env().build().unwrap();
+        assert_eq!(None, env.require::<Option<String>>("PATH").unwrap());
+    }
+
+    #[test]
+    fn borrowed_get_test() {
+        let env = Salak::builder()
+            .set("a", "plain")
+            .set("b", "${a}")
+            .set("c", "\\${a\\}")
+            .build()
+            .unwrap();
+        match env.reg.get(&mut Key::from_str("a"), None) {
+            Ok(Some(Property::S(v))) => assert_eq!("plain", v),
+            v => panic!("unexpected {:?}", v.map(|_| ())),
+        }
+        match env.reg.get(&mut Key::from_str("b"), None) {
+            Ok(Some(Property::O(v))) => assert_eq!("plain", v),
+            v => panic!("unexpected {:?}", v.map(|_| ())),
+        }
+        assert_eq!("${a}", env.require::<String>("c").unwrap());
+    }
+
+    #[test]
+    fn push_key_test() {
+        struct Pairs(Vec<(String, u8)>);
+
+        impl FromEnvironment for Pairs {
+            fn from_env(_: Option<Property<'_>>, env: &mut SalakContext<'_>) -> Res<Self> {
+                let mut pairs = vec![];
+                for k in env.get_sub_keys().str_keys() {
+                    let mut group = env.push_key(k);
+                    let max = group.get_sub_keys().max().unwrap_or(0);
+                    for i in 0..=max {
+                        let mut item = group.push_index(i);
+                        assert_eq!(format!("pairs.{}[{}]", k, i), item.current_key());
+                        pairs.push((k.to_string(), item.require_def("", None)?));
+                    }
+                    group.pop_key();
+                    assert_eq!("pairs", env.current_key());
+                }
+                pairs.sort();
+                Ok(Pairs(pairs))
+            }
+        }
+
+        let env = Salak::builder()
+            .set("pairs.a[0]", "1")
+            .set("pairs.a[1]", "2")
+            .set("pairs.b[0]", "3")
+            .build()
+            .unwrap();
+        let pairs = env.require::<Pairs>("pairs").unwrap();
+        assert_eq!(
+            vec![
+                ("a".to_string(), 1),
+                ("a".to_string(), 2),
+                ("b".to_string(), 3)
+            ],
+            pairs.0
+        );
     }
 }
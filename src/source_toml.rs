@@ -1,36 +1,222 @@
-use toml::Value;
+use serde::de::{Deserialize, Deserializer, Error, MapAccess, SeqAccess, Visitor};
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+};
+use toml::{value::Datetime, Spanned, Value};
 
 use crate::{
-    source_raw::FileItem, Key, Property, PropertyError, PropertySource, Res, SubKey, SubKeys,
+    source_raw::{FileItem, FileStamp},
+    Key, Property, PropertyError, PropertySource, Res, SubKey, SubKeys,
 };
 
 #[derive(Debug)]
 pub(crate) struct Toml {
     item: FileItem,
+    stamp: FileStamp,
     name: String,
     value: Value,
+    lines: HashMap<String, usize>,
 }
 
 impl Toml {
     pub(crate) fn new(item: FileItem) -> Res<Self> {
+        let (value, lines) = parse(&item.load()?)?;
+        if let Some(key) = duplicate_key(&value) {
+            return Err(PropertyError::parse_fail(&format!(
+                "duplicate key {} in {}",
+                key,
+                item.name()
+            )));
+        }
         Ok(Toml {
+            stamp: FileStamp::new(&item),
             name: item.name(),
-            value: toml::from_str(&item.load()?)?,
+            value,
+            lines,
             item,
         })
     }
 }
 
+/// Find keys defined twice after flattening, such as quoted key `"a.b"` and `b` in table `[a]`,
+/// which toml parser accepts as different keys.
+fn duplicate_key(value: &Value) -> Option<String> {
+    fn walk(value: &Value, key: &mut Key<'_>, seen: &mut HashSet<String>) -> Option<String> {
+        let mut visit = |key: &mut Key<'_>, v: &Value| {
+            if !seen.insert(key.as_str().to_string()) {
+                return Some(key.as_str().to_string());
+            }
+            walk(v, key, seen)
+        };
+        match value {
+            Value::Table(t) => {
+                for (k, v) in t {
+                    key.push(SubKey::S(k));
+                    let dup = visit(key, v);
+                    key.pop();
+                    if dup.is_some() {
+                        return dup;
+                    }
+                }
+            }
+            Value::Array(vs) => {
+                for (i, v) in vs.iter().enumerate() {
+                    key.push(SubKey::I(i));
+                    let dup = visit(key, v);
+                    key.pop();
+                    if dup.is_some() {
+                        return dup;
+                    }
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+    walk(value, &mut Key::new(), &mut HashSet::new())
+}
+
+/// Toml value with spans of values, so value and locations of keys are got by parsing once.
+enum Spans {
+    Table(Vec<(String, Spanned<Spans>)>),
+    Array(Vec<Spanned<Spans>>),
+    Value(Value),
+}
+
+impl<'de> Deserialize<'de> for Spans {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SpansVisitor;
+
+        impl<'de> Visitor<'de> for SpansVisitor {
+            type Value = Spans;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("toml value")
+            }
+
+            fn visit_bool<E>(self, v: bool) -> Result<Spans, E> {
+                Ok(Spans::Value(Value::Boolean(v)))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Spans, E> {
+                Ok(Spans::Value(Value::Integer(v)))
+            }
+
+            fn visit_u64<E: Error>(self, v: u64) -> Result<Spans, E> {
+                match i64::try_from(v) {
+                    Ok(v) => Ok(Spans::Value(Value::Integer(v))),
+                    _ => Err(E::custom("u64 value was too large")),
+                }
+            }
+
+            fn visit_f64<E>(self, v: f64) -> Result<Spans, E> {
+                Ok(Spans::Value(Value::Float(v)))
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Spans, E> {
+                Ok(Spans::Value(Value::String(v.to_string())))
+            }
+
+            fn visit_string<E>(self, v: String) -> Result<Spans, E> {
+                Ok(Spans::Value(Value::String(v)))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Spans, A::Error> {
+                let mut vs = vec![];
+                while let Some(v) = seq.next_element()? {
+                    vs.push(v);
+                }
+                Ok(Spans::Array(vs))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Spans, A::Error> {
+                let mut vs = vec![];
+                let mut keys = HashSet::new();
+                while let Some(k) = map.next_key::<String>()? {
+                    // Datetime is deserialized as a map with a private key.
+                    if k == "$__toml_private_datetime" {
+                        let v = map.next_value::<String>()?;
+                        return match v.parse::<Datetime>() {
+                            Ok(v) => Ok(Spans::Value(Value::Datetime(v))),
+                            Err(e) => Err(A::Error::custom(e)),
+                        };
+                    }
+                    if !keys.insert(k.clone()) {
+                        return Err(A::Error::custom(format!("duplicate key: `{}`", k)));
+                    }
+                    vs.push((k, map.next_value()?));
+                }
+                Ok(Spans::Table(vs))
+            }
+        }
+
+        deserializer.deserialize_any(SpansVisitor)
+    }
+}
+
+/// Parse toml value with line numbers of keys by spans of values from toml parser. Tables
+/// defined by headers, such as `[server]`, have no spans, they are located by their parent keys.
+fn parse(content: &str) -> Res<(Value, HashMap<String, usize>)> {
+    fn walk(
+        spans: &Spans,
+        key: &mut Key<'_>,
+        newlines: &[usize],
+        lines: &mut HashMap<String, usize>,
+    ) -> Value {
+        let mut visit = |key: &mut Key<'_>, v: &Spanned<Spans>| {
+            if v.end() > 0 {
+                let line = newlines.partition_point(|p| *p < v.start()) + 1;
+                lines.insert(key.as_str().to_string(), line);
+            }
+            walk(v.get_ref(), key, newlines, lines)
+        };
+        match spans {
+            Spans::Table(vs) => Value::Table(
+                vs.iter()
+                    .map(|(k, v)| {
+                        key.push(SubKey::S(k));
+                        let v = visit(key, v);
+                        key.pop();
+                        (k.clone(), v)
+                    })
+                    .collect(),
+            ),
+            Spans::Array(vs) => Value::Array(
+                vs.iter()
+                    .enumerate()
+                    .map(|(i, v)| {
+                        key.push(SubKey::I(i));
+                        let v = visit(key, v);
+                        key.pop();
+                        v
+                    })
+                    .collect(),
+            ),
+            Spans::Value(v) => v.clone(),
+        }
+    }
+    let newlines: Vec<usize> = content.match_indices('\n').map(|(i, _)| i).collect();
+    let mut lines = HashMap::new();
+    let value = walk(
+        &toml::from_str(content)?,
+        &mut Key::new(),
+        &newlines,
+        &mut lines,
+    );
+    Ok((value, lines))
+}
+
 fn sub_value<'a>(toml: &'a Toml, key: &Key<'_>) -> Option<&'a Value> {
     let mut val = &toml.value;
     for n in key.iter() {
         match n {
             SubKey::S(n) => match val {
-                Value::Table(t) => val = t.get(*n)?,
+                Value::Table(t) => val = t.get(n)?,
                 _ => return None,
             },
             SubKey::I(n) => match val {
-                Value::Array(vs) => val = vs.get(*n)?,
+                Value::Array(vs) => val = vs.get(n)?,
                 _ => return None,
             },
         }
@@ -46,7 +232,7 @@ impl PropertySource for Toml {
     fn get_property(&self, key: &Key<'_>) -> Option<Property<'_>> {
         match sub_value(self, key)? {
             Value::String(vs) => Some(Property::S(vs)),
-            Value::Integer(vs) => Some(Property::I(*vs)),
+            Value::Integer(vs) => Some(Property::I((*vs).into())),
             Value::Float(vs) => Some(Property::F(*vs)),
             Value::Boolean(vs) => Some(Property::B(*vs)),
             Value::Datetime(vs) => Some(Property::O(vs.to_string())),
@@ -57,7 +243,7 @@ impl PropertySource for Toml {
     fn get_sub_keys<'a>(&'a self, key: &Key<'_>, sub_keys: &mut SubKeys<'a>) {
         match sub_value(self, key) {
             Some(Value::Table(t)) => t.keys().for_each(|f| sub_keys.insert(f.as_str())),
-            Some(Value::Array(vs)) => sub_keys.insert(vs.len()),
+            Some(Value::Array(vs)) if !vs.is_empty() => sub_keys.insert(vs.len() - 1),
             _ => {}
         }
     }
@@ -69,8 +255,18 @@ impl PropertySource for Toml {
         }
     }
 
+    fn location(&self, key: &Key<'_>) -> Option<String> {
+        let mut key = key.as_str();
+        loop {
+            if let Some(line) = self.lines.get(key) {
+                return Some(format!("{}:{}", self.name, line));
+            }
+            key = &key[..key.rfind(['.', '['])?];
+        }
+    }
+
     fn reload_source(&self) -> Result<Option<Box<dyn PropertySource>>, PropertyError> {
-        Ok(Some(Box::new(Toml::new(self.item.clone())?)))
+        self.stamp.reload(&self.item, Toml::new)
     }
 }
 
@@ -82,3 +278,394 @@ macro_rules! inline_toml {
         $crate::Toml::new(format!("inline_toml:{}", $x), include_str!($x)).unwrap()
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn typed_value_test() {
+        let path = std::env::temp_dir().join(format!(
+            "salak_typed_value_test_{}.toml",
+            std::process::id()
+        ));
+        std::fs::write(
+            &path,
+            "int = 9223372036854775807\nneg = -3\nfloat = 1.5\nbool = true\nstr = \"true\"\ndate = 1979-05-27T07:32:00Z\n",
+        )
+        .unwrap();
+        let toml = Toml::new(FileItem(path)).unwrap();
+        let key = |k: &'static str| Key::from_str(k);
+        assert!(matches!(
+            toml.get_property(&key("neg")),
+            Some(Property::I(-3))
+        ));
+        assert!(matches!(
+            toml.get_property(&key("float")),
+            Some(Property::F(_))
+        ));
+        assert!(matches!(
+            toml.get_property(&key("bool")),
+            Some(Property::B(true))
+        ));
+        assert!(matches!(
+            toml.get_property(&key("str")),
+            Some(Property::S("true"))
+        ));
+        assert!(matches!(
+            toml.get_property(&key("date")),
+            Some(Property::O(v)) if v == "1979-05-27T07:32:00Z"
+        ));
+
+        let mut env = Salak::builder().build().unwrap();
+        env.register(toml);
+        assert_eq!(i64::MAX, env.require::<i64>("int").unwrap());
+        assert_eq!(-3, env.require::<i8>("neg").unwrap());
+        assert_eq!(1.5, env.require::<f64>("float").unwrap());
+        assert_eq!(true, env.require::<bool>("bool").unwrap());
+        assert_eq!(true, env.require::<bool>("str").unwrap());
+        assert_eq!("1.5", env.require::<String>("float").unwrap());
+        assert!(env.require::<bool>("neg").is_err());
+    }
+
+    #[test]
+    fn include_test() {
+        let dir = std::env::temp_dir().join(format!("salak_include_test_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("conf")).unwrap();
+        std::fs::write(
+            dir.join("app.toml"),
+            "salak.include = [\"conf/db.toml\"]\na = 1\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("conf/db.toml"),
+            "salak.include = \"cache.toml\"\na = 2\ndb.port = 5432\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("conf/cache.toml"), "cache.size = 10\n").unwrap();
+        let env = Salak::builder()
+            .set("salak.app.dir", dir.to_str().unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(1, env.require::<u8>("a").unwrap());
+        assert_eq!(5432, env.require::<u16>("db.port").unwrap());
+        assert_eq!(10, env.require::<u16>("cache.size").unwrap());
+
+        std::fs::write(dir.join("conf/cache.toml"), "salak.include = \"db.toml\"\n").unwrap();
+        assert!(Salak::builder()
+            .set("salak.app.dir", dir.to_str().unwrap())
+            .build()
+            .is_err());
+        std::fs::write(
+            dir.join("conf/cache.toml"),
+            "salak.include = \"none.toml\"\n",
+        )
+        .unwrap();
+        assert!(Salak::builder()
+            .set("salak.app.dir", dir.to_str().unwrap())
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn empty_file_test() {
+        let dir =
+            std::env::temp_dir().join(format!("salak_empty_file_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("app.toml"), "# nothing here\n").unwrap();
+        let env = Salak::builder()
+            .set("salak.app.dir", dir.to_str().unwrap())
+            .build()
+            .unwrap();
+        assert!(env.require::<Option<u8>>("a").unwrap().is_none());
+        let err = Salak::builder()
+            .set("salak.app.dir", dir.to_str().unwrap())
+            .configure_empty_file_error(true)
+            .build()
+            .err()
+            .unwrap();
+        assert!(format!("{:?}", err).contains("app.toml"));
+
+        std::fs::write(dir.join("app.toml"), "a = 1\n").unwrap();
+        let env = Salak::builder()
+            .set("salak.app.dir", dir.to_str().unwrap())
+            .configure_empty_file_error(true)
+            .build()
+            .unwrap();
+        assert_eq!(1, env.require::<u8>("a").unwrap());
+    }
+
+    #[test]
+    fn filenames_test() {
+        let dir = std::env::temp_dir().join(format!("salak_filenames_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("app.toml"), "a = 1\nb = 1\n").unwrap();
+        std::fs::write(dir.join("config.toml"), "a = 2\n").unwrap();
+        std::fs::write(dir.join("config-dev.toml"), "c = 3\n").unwrap();
+        let env = Salak::builder()
+            .set("salak.app.dir", dir.to_str().unwrap())
+            .set("salak.app.profile", "dev")
+            .set("salak.app.filenames.toml", "config")
+            .set("salak.app.filenames.yaml", "secrets")
+            .build()
+            .unwrap();
+        assert_eq!(2, env.require::<u8>("a").unwrap());
+        assert_eq!(None, env.require::<Option<u8>>("b").unwrap());
+        assert_eq!(3, env.require::<u8>("c").unwrap());
+    }
+
+    #[test]
+    fn builder_from_env_test() {
+        let dir = std::env::temp_dir().join(format!(
+            "salak_builder_from_env_test_{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("myapp.toml"), "a = 1\nb = 1\n").unwrap();
+        std::fs::write(dir.join("myapp-dev.toml"), "a = 2\n").unwrap();
+        let _guard = lock_env();
+        std::env::set_var("SALAK_CONFIG_DIR", dir.to_str().unwrap());
+        std::env::set_var("SALAK_CONFIG_FILE", "myapp");
+        std::env::set_var("SALAK_PROFILE", "dev");
+        let env = Salak::builder_from_env().build().unwrap();
+        assert_eq!(2, env.require::<u8>("a").unwrap());
+        assert_eq!(1, env.require::<u8>("b").unwrap());
+        assert_eq!("dev", env.require::<String>("salak.app.profile").unwrap());
+
+        let env = Salak::builder_from_env()
+            .set("salak.app.profile", "default")
+            .build()
+            .unwrap();
+        assert_eq!(1, env.require::<u8>("a").unwrap());
+        std::env::remove_var("SALAK_CONFIG_DIR");
+        std::env::remove_var("SALAK_CONFIG_FILE");
+        std::env::remove_var("SALAK_PROFILE");
+    }
+
+    #[test]
+    fn set_profile_from_env_var_test() {
+        let dir =
+            std::env::temp_dir().join(format!("salak_profile_env_var_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("app.toml"), "a = 1\n").unwrap();
+        std::fs::write(dir.join("app-staging.toml"), "a = 2\n").unwrap();
+        let build = || {
+            Salak::builder()
+                .set("salak.app.dir", dir.to_str().unwrap())
+                .set_profile_from_env_var("SALAK_TEST_APP_ENV")
+                .build()
+                .unwrap()
+        };
+        let _guard = lock_env();
+        assert_eq!(1, build().require::<u8>("a").unwrap());
+        std::env::set_var("SALAK_TEST_APP_ENV", "staging");
+        let env = build();
+        assert_eq!(2, env.require::<u8>("a").unwrap());
+        assert_eq!(
+            "staging",
+            env.require::<String>("salak.app.profile").unwrap()
+        );
+        std::env::remove_var("SALAK_TEST_APP_ENV");
+    }
+
+    #[test]
+    fn env_override_array_test() {
+        let path = std::env::temp_dir().join(format!(
+            "salak_env_override_array_test_{}.toml",
+            std::process::id()
+        ));
+        std::fs::write(
+            &path,
+            "salak_env_servers = [\"a\", \"b\"]\n[[salak_env_hosts]]\nport = 1\n",
+        )
+        .unwrap();
+        let mut vars = HashMap::new();
+        vars.insert("salak_env_servers__1".to_string(), "x".to_string());
+        vars.insert("salak_env_hosts__0__port".to_string(), "2".to_string());
+        let mut env = Salak::builder().replace_env_map(vars).build().unwrap();
+        env.register(Toml::new(FileItem(path)).unwrap());
+        assert_eq!(
+            vec!["a", "x"],
+            env.require::<Vec<String>>("salak_env_servers").unwrap()
+        );
+        assert_eq!(2, env.require::<u16>("salak_env_hosts[0].port").unwrap());
+    }
+
+    #[cfg(feature = "derive")]
+    #[derive(FromEnvironment, Debug)]
+    struct ServerConfig {
+        host: String,
+        #[salak(default = "80")]
+        port: u16,
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn array_of_tables_test() {
+        let path = std::env::temp_dir().join(format!(
+            "salak_array_of_tables_test_{}.toml",
+            std::process::id()
+        ));
+        std::fs::write(
+            &path,
+            "[[servers]]\nhost = \"a\"\nport = 8080\n\n[[servers]]\nhost = \"b\"\n",
+        )
+        .unwrap();
+        let toml = Toml::new(FileItem(path)).unwrap();
+        let mut sub_keys = SubKeys::new();
+        toml.get_sub_keys(&Key::from_str("servers"), &mut sub_keys);
+        assert_eq!(Some(1), sub_keys.max());
+
+        let mut env = Salak::builder().build().unwrap();
+        env.register(toml);
+        let servers = env.require::<Vec<ServerConfig>>("servers").unwrap();
+        assert_eq!(2, servers.len());
+        assert_eq!(("a", 8080), (servers[0].host.as_str(), servers[0].port));
+        assert_eq!(("b", 80), (servers[1].host.as_str(), servers[1].port));
+        assert_eq!("b", env.require::<String>("servers[1].host").unwrap());
+    }
+
+    #[test]
+    fn duplicate_key_test() {
+        let path = std::env::temp_dir().join(format!(
+            "salak_duplicate_key_test_{}.toml",
+            std::process::id()
+        ));
+        std::fs::write(&path, "[server]\nport = 1\nport = 2\n").unwrap();
+        let err = Toml::new(FileItem(path.clone())).err().unwrap();
+        assert!(format!("{:?}", err).contains("duplicate key: `port`"));
+
+        std::fs::write(&path, "\"a.b\" = 1\n\n[a]\nb = 2\n").unwrap();
+        let err = Toml::new(FileItem(path.clone())).err().unwrap();
+        assert!(format!("{:?}", err).contains("duplicate key a.b"));
+
+        std::fs::write(&path, "\"a.b\" = 1\n\n[a]\nc = 2\n").unwrap();
+        assert!(Toml::new(FileItem(path.clone())).is_ok());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn unchanged_reload_test() {
+        let path = std::env::temp_dir().join(format!(
+            "salak_unchanged_reload_test_{}.toml",
+            std::process::id()
+        ));
+        std::fs::write(&path, "a = 1\n").unwrap();
+        let toml = Toml::new(FileItem(path.clone())).unwrap();
+        assert!(toml.reload_source().unwrap().is_none());
+
+        std::fs::write(&path, "a = 22\n").unwrap();
+        let toml = toml.reload_source().unwrap().unwrap();
+        assert!(matches!(
+            toml.get_property(&Key::from_str("a")),
+            Some(Property::I(22))
+        ));
+        assert!(toml.reload_source().unwrap().is_none());
+
+        let item = FileItem(path.clone());
+        let stamp = FileStamp::new(&item);
+        let mut loaded = 0;
+        for _ in 0..2 {
+            let toml = stamp
+                .reload(&item, |item| {
+                    loaded += 1;
+                    Toml::new(item)
+                })
+                .unwrap();
+            assert!(toml.is_none());
+        }
+        std::fs::write(&path, "a = 333\n").unwrap();
+        for i in 0..2 {
+            let toml = stamp
+                .reload(&item, |item| {
+                    loaded += 1;
+                    Toml::new(item)
+                })
+                .unwrap();
+            match toml {
+                Some(toml) if i == 0 => assert!(matches!(
+                    toml.get_property(&Key::from_str("a")),
+                    Some(Property::I(333))
+                )),
+                v => assert!(v.is_none()),
+            }
+        }
+        assert_eq!(1, loaded);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn registered_reload_test() {
+        let path = std::env::temp_dir().join(format!(
+            "salak_registered_reload_test_{}.toml",
+            std::process::id()
+        ));
+        std::fs::write(&path, "a = 1\n").unwrap();
+        let mut env = Salak::new().unwrap();
+        env.register(Toml::new(FileItem(path.clone())).unwrap());
+        let a = env.require::<wrapper::IORef<u16>>("a").unwrap();
+        std::fs::write(&path, "a = 22\n").unwrap();
+        for i in 0..2 {
+            assert_eq!(i == 0, env.reload().unwrap());
+            assert_eq!(22, a.get_val().unwrap());
+        }
+        std::fs::write(&path, "a = 333\n").unwrap();
+        let report = env.reload_detailed().unwrap();
+        assert_eq!(1, report.sources.len());
+        assert_eq!(333, a.get_val().unwrap());
+        let report = env.reload_detailed().unwrap();
+        assert!(report.sources.is_empty());
+        assert_eq!(333, a.get_val().unwrap());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn location_test() {
+        let path =
+            std::env::temp_dir().join(format!("salak_location_test_{}.toml", std::process::id()));
+        std::fs::write(
+            &path,
+            "a = 1\n\n[server]\nport = \"x\"\nhosts = [\n  \"a\",\n]\n\n[[db]]\nport = 1\n[[db]]\nport = \"y\"\n[x]\ny.z = 1\nw = {\"v\" = 2}\n",
+        )
+        .unwrap();
+        let toml = Toml::new(FileItem(path.clone())).unwrap();
+        let location = |k: &str| toml.location(&Key::from_str(k));
+        let name = path.display().to_string();
+        assert_eq!(Some(format!("{}:1", name)), location("a"));
+        assert_eq!(Some(format!("{}:4", name)), location("server.port"));
+        assert_eq!(Some(format!("{}:5", name)), location("server.hosts"));
+        assert_eq!(Some(format!("{}:6", name)), location("server.hosts[0]"));
+        assert_eq!(Some(format!("{}:12", name)), location("db[1].port"));
+        assert_eq!(Some(format!("{}:14", name)), location("x.y.z"));
+        assert_eq!(Some(format!("{}:15", name)), location("x.w.v"));
+        assert_eq!(None, location("x"));
+        assert_eq!(None, location("b"));
+
+        let mut env = Salak::builder().build().unwrap();
+        env.register(Toml::new(FileItem(path.clone())).unwrap());
+        match env.require::<u16>("server.port") {
+            Err(PropertyError::ParseFail(Some(key), e)) => {
+                assert_eq!("server.port", key);
+                assert!(!e.to_string().contains(&name));
+            }
+            v => panic!("unexpected {:?}", v),
+        }
+
+        let mut env = Salak::builder()
+            .configure_error_location(true)
+            .build()
+            .unwrap();
+        env.register(Toml::new(FileItem(path)).unwrap());
+        env.reload().unwrap();
+        for (key, line) in &[("server.port", 4), ("db[1].port", 12)] {
+            match env.require::<u16>(key) {
+                Err(PropertyError::ParseFail(Some(_), e)) => {
+                    assert!(e.to_string().ends_with(&format!("at {}:{}", name, line)));
+                    assert!(e.source().is_some());
+                }
+                v => panic!("unexpected {:?}", v),
+            }
+        }
+    }
+}
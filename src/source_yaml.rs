@@ -1,25 +1,168 @@
-use yaml_rust::Yaml;
+use std::collections::HashSet;
+use yaml_rust::{
+    parser::{Event, EventReceiver, Parser},
+    yaml::Hash,
+    Yaml,
+};
 
 use crate::{
-    source_raw::FileItem, Key, Property, PropertyError, PropertySource, Res, SubKey, SubKeys,
+    source_raw::{FileItem, FileStamp},
+    Key, Property, PropertyError, PropertySource, Res, SubKey, SubKeys,
 };
 
 pub(crate) struct YamlValue {
     item: FileItem,
+    stamp: FileStamp,
     name: String,
     value: Vec<Yaml>,
 }
 
 impl YamlValue {
     pub(crate) fn new(item: FileItem) -> Res<Self> {
+        let stamp = FileStamp::new(&item);
+        let content = item.load()?;
+        if let Some(key) = DuplicateKeys::check(&content) {
+            return Err(PropertyError::parse_fail(&format!(
+                "duplicate key {} in {}",
+                key,
+                item.name()
+            )));
+        }
         Ok(Self {
+            stamp,
             name: item.name(),
-            value: yaml_rust::YamlLoader::load_from_str(&item.load()?)?,
+            value: yaml_rust::YamlLoader::load_from_str(&content)?
+                .into_iter()
+                .map(merge_keys)
+                .collect(),
             item,
         })
     }
 }
 
+/// Frames are only kept for nesting levels, so the size difference of variants is fine.
+#[allow(variant_size_differences)]
+enum Frame {
+    Map(HashSet<String>, bool),
+    Seq(usize),
+}
+
+/// Detect duplicate keys in the same mapping, which are silently
+/// overwritten by yaml loader.
+#[derive(Default)]
+struct DuplicateKeys {
+    stack: Vec<Frame>,
+    path: Vec<String>,
+    dup: Option<String>,
+}
+
+impl DuplicateKeys {
+    fn check(content: &str) -> Option<String> {
+        let mut recv = DuplicateKeys::default();
+        let _ = Parser::new(content.chars()).load(&mut recv, true);
+        recv.dup
+    }
+
+    fn begin_value(&mut self) {
+        if let Some(Frame::Seq(i)) = self.stack.last_mut() {
+            self.path.push(format!("[{}]", i));
+            *i += 1;
+        }
+    }
+
+    fn end_value(&mut self) {
+        match self.stack.last_mut() {
+            Some(Frame::Map(_, expect_key)) => {
+                *expect_key = true;
+                self.path.pop();
+            }
+            Some(Frame::Seq(_)) => {
+                self.path.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+impl EventReceiver for DuplicateKeys {
+    fn on_event(&mut self, ev: Event) {
+        match ev {
+            Event::DocumentStart => {
+                self.stack.clear();
+                self.path.clear();
+            }
+            Event::Scalar(key, ..) => match self.stack.last_mut() {
+                Some(Frame::Map(keys, expect_key)) if *expect_key => {
+                    *expect_key = false;
+                    let dup = !keys.insert(key.clone());
+                    self.path.push(key);
+                    if dup && self.dup.is_none() {
+                        self.dup = Some(self.path.join(".").replace(".[", "["));
+                    }
+                }
+                _ => {
+                    self.begin_value();
+                    self.end_value();
+                }
+            },
+            Event::Alias(_) => {
+                self.begin_value();
+                self.end_value();
+            }
+            Event::MappingStart(_) => {
+                self.begin_value();
+                self.stack.push(Frame::Map(HashSet::new(), true));
+            }
+            Event::SequenceStart(_) => {
+                self.begin_value();
+                self.stack.push(Frame::Seq(0));
+            }
+            Event::MappingEnd | Event::SequenceEnd => {
+                self.stack.pop();
+                self.end_value();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Resolve merge keys `<<`, explicit keys take precedence over merged ones,
+/// and earlier merged maps take precedence over later ones.
+fn merge_keys(val: Yaml) -> Yaml {
+    match val {
+        Yaml::Hash(t) => {
+            let merge = Yaml::String("<<".to_string());
+            let mut hash = Hash::new();
+            let mut merged = vec![];
+            for (k, v) in t {
+                match (k == merge, merge_keys(v)) {
+                    (true, Yaml::Hash(m)) => merged.push(m),
+                    (true, Yaml::Array(vs)) if vs.iter().all(|v| v.as_hash().is_some()) => {
+                        for v in vs {
+                            if let Yaml::Hash(m) = v {
+                                merged.push(m);
+                            }
+                        }
+                    }
+                    (_, v) => {
+                        hash.insert(k, v);
+                    }
+                }
+            }
+            for m in merged {
+                for (k, v) in m {
+                    if !hash.contains_key(&k) {
+                        hash.insert(k, v);
+                    }
+                }
+            }
+            Yaml::Hash(hash)
+        }
+        Yaml::Array(vs) => Yaml::Array(vs.into_iter().map(merge_keys).collect()),
+        v => v,
+    }
+}
+
 fn sub_value<'a>(mut val: &'a Yaml, key: &Key<'_>) -> Option<&'a Yaml> {
     for n in key.iter() {
         match n {
@@ -28,7 +171,7 @@ fn sub_value<'a>(mut val: &'a Yaml, key: &Key<'_>) -> Option<&'a Yaml> {
                 _ => return None,
             },
             SubKey::I(n) => match val {
-                Yaml::Array(vs) => val = vs.get(*n)?,
+                Yaml::Array(vs) => val = vs.get(n)?,
                 _ => return None,
             },
         }
@@ -46,9 +189,13 @@ impl PropertySource for YamlValue {
             if let Some(v) = sub_value(v, key) {
                 return match v {
                     Yaml::String(vs) => Some(Property::S(vs)),
-                    Yaml::Integer(vs) => Some(Property::I(*vs)),
-                    Yaml::Real(vs) => Some(Property::S(vs)),
+                    Yaml::Integer(vs) => Some(Property::I((*vs).into())),
+                    Yaml::Real(vs) => match v.as_f64() {
+                        Some(f) => Some(Property::F(f)),
+                        _ => Some(Property::S(vs)),
+                    },
                     Yaml::Boolean(vs) => Some(Property::B(*vs)),
+                    Yaml::Null => None,
                     _ => continue,
                 };
             }
@@ -65,7 +212,7 @@ impl PropertySource for YamlValue {
                             sub_keys.insert(v);
                         }
                     }),
-                    Yaml::Array(vs) => sub_keys.insert(vs.len()),
+                    Yaml::Array(vs) if !vs.is_empty() => sub_keys.insert(vs.len() - 1),
                     _ => continue,
                 }
             }
@@ -83,6 +230,137 @@ impl PropertySource for YamlValue {
     }
 
     fn reload_source(&self) -> Result<Option<Box<dyn PropertySource>>, PropertyError> {
-        Ok(Some(Box::new(YamlValue::new(self.item.clone())?)))
+        self.stamp.reload(&self.item, YamlValue::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+
+    #[test]
+    fn typed_value_test() {
+        let path = std::env::temp_dir().join(format!(
+            "salak_typed_value_test_{}.yaml",
+            std::process::id()
+        ));
+        std::fs::write(&path, "neg: -3\nfloat: 1.5\nbool: true\nstr: \"true\"\n").unwrap();
+        let yaml = YamlValue::new(FileItem(path)).unwrap();
+        let key = |k: &'static str| Key::from_str(k);
+        assert!(matches!(
+            yaml.get_property(&key("neg")),
+            Some(Property::I(-3))
+        ));
+        assert!(matches!(
+            yaml.get_property(&key("float")),
+            Some(Property::F(_))
+        ));
+        assert!(matches!(
+            yaml.get_property(&key("bool")),
+            Some(Property::B(true))
+        ));
+        assert!(matches!(
+            yaml.get_property(&key("str")),
+            Some(Property::S("true"))
+        ));
+
+        let mut env = Salak::builder().build().unwrap();
+        env.register(yaml);
+        assert_eq!(-3, env.require::<i8>("neg").unwrap());
+        assert_eq!(1.5, env.require::<f64>("float").unwrap());
+        assert!(env.require::<bool>("bool").unwrap());
+        assert!(env.require::<bool>("str").unwrap());
+        assert_eq!("1.5", env.require::<String>("float").unwrap());
+        assert!(env.require::<u8>("float").is_ok());
+        assert!(env.require::<bool>("neg").is_err());
+    }
+
+    #[test]
+    fn explicit_null_test() {
+        let path = std::env::temp_dir().join(format!(
+            "salak_explicit_null_test_{}.yaml",
+            std::process::id()
+        ));
+        std::fs::write(
+            &path,
+            "a: ~\nb:\nc: \"\"\nlist: [x, ~, y]\nserver:\n  port: ~\n",
+        )
+        .unwrap();
+        let mut env = Salak::builder().build().unwrap();
+        env.register(YamlValue::new(FileItem(path)).unwrap());
+        env.register(source::HashMapSource::new("default").set("a", "default"));
+        assert_eq!("default", env.require::<String>("a").unwrap());
+        assert_eq!(None, env.require::<Option<String>>("b").unwrap());
+        assert_eq!(
+            Some(String::new()),
+            env.require::<Option<String>>("c").unwrap()
+        );
+        assert_eq!(None, env.require::<Option<u16>>("c").unwrap());
+        #[cfg(feature = "derive")]
+        {
+            #[derive(FromEnvironment)]
+            #[salak(prefix = "server")]
+            struct Server {
+                #[salak(default = 8080)]
+                port: u16,
+            }
+            assert_eq!(8080, env.get::<Server>().unwrap().port);
+        }
+        assert_eq!(
+            vec![Some("x".to_string()), None, Some("y".to_string())],
+            env.require::<Vec<Option<String>>>("list").unwrap()
+        );
+    }
+
+    #[test]
+    fn merge_key_test() {
+        let path =
+            std::env::temp_dir().join(format!("salak_merge_key_test_{}.yaml", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"
+base: &base
+  host: localhost
+  port: 5432
+extra: &extra
+  port: 1
+  pool: 8
+dev:
+  <<: *base
+  name: dev
+prod:
+  <<: [*base, *extra]
+  host: db.prod
+"#,
+        )
+        .unwrap();
+        let mut env = Salak::builder().build().unwrap();
+        env.register(YamlValue::new(FileItem(path)).unwrap());
+        assert_eq!("localhost", env.require::<String>("dev.host").unwrap());
+        assert_eq!(5432, env.require::<u16>("dev.port").unwrap());
+        assert_eq!("dev", env.require::<String>("dev.name").unwrap());
+        assert_eq!(None, env.require::<Option<String>>("dev.<<").unwrap());
+        assert_eq!("db.prod", env.require::<String>("prod.host").unwrap());
+        assert_eq!(5432, env.require::<u16>("prod.port").unwrap());
+        assert_eq!(8, env.require::<u8>("prod.pool").unwrap());
+    }
+
+    #[test]
+    fn duplicate_key_test() {
+        let path = std::env::temp_dir().join(format!(
+            "salak_duplicate_key_test_{}.yaml",
+            std::process::id()
+        ));
+        std::fs::write(
+            &path,
+            "server:\n  host: a\n  port: 1\nlist:\n  - port: 1\n    port: 2\n",
+        )
+        .unwrap();
+        let err = YamlValue::new(FileItem(path.clone())).err().unwrap();
+        assert!(format!("{:?}", err).contains("duplicate key list[0].port"));
+
+        std::fs::write(&path, "a:\n  b: 1\nc:\n  b: 2\n---\na: 3\n").unwrap();
+        assert!(YamlValue::new(FileItem(path)).is_ok());
     }
 }
@@ -0,0 +1,35 @@
+use std::sync::Mutex;
+
+lazy_static::lazy_static! {
+    static ref LOGS: Mutex<Vec<(log::Level, String)>> = Mutex::new(vec![]);
+}
+
+struct Sink;
+
+impl log::Log for Sink {
+    fn enabled(&self, _: &log::Metadata<'_>) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record<'_>) {
+        LOGS.lock()
+            .unwrap()
+            .push((record.level(), record.args().to_string()));
+    }
+
+    fn flush(&self) {}
+}
+
+/// Capture logs of all tests, logger can only be set once, so it is shared by tests.
+pub(crate) fn init() {
+    let _ = log::set_logger(&Sink);
+    log::set_max_level(log::LevelFilter::Debug);
+}
+
+/// Check if any captured log with `level` matches `f`.
+pub(crate) fn any_log(level: log::Level, f: impl Fn(&str) -> bool) -> bool {
+    LOGS.lock()
+        .unwrap()
+        .iter()
+        .any(|(l, v)| *l == level && f(v))
+}
@@ -0,0 +1,72 @@
+//! Utilities for testing with [`Salak`].
+//!
+//! [`TestEnv`] builds hermetic environments, only the given properties and
+//! random source are available, so neither process environment variables
+//! nor configuration files will leak into tests.
+//!
+//! ```
+//! use salak::{testing::TestEnv, *};
+//!
+//! let env = TestEnv::build(&[
+//!     ("app.host", "localhost"),
+//!     ("app.url", "http://${app.host}:${app.port:8080}"),
+//! ])
+//! .unwrap();
+//! assert_eq!(
+//!     "http://localhost:8080",
+//!     env.require::<String>("app.url").unwrap()
+//! );
+//! assert_eq!(None, env.require::<Option<String>>("PATH").unwrap());
+//! ```
+use crate::{Res, Salak, SalakBuilder};
+
+/// Hermetic environment builder for tests.
+#[allow(missing_copy_implementations)]
+#[derive(Debug)]
+pub struct TestEnv;
+
+impl TestEnv {
+    /// Create a [`SalakBuilder`] with `props`, file source and system environment source are disabled.
+    /// Use this builder for registering resources.
+    pub fn builder(props: &[(&str, &str)]) -> SalakBuilder {
+        let mut builder = Salak::builder().disable_system_env();
+        #[cfg(any(feature = "toml", feature = "yaml", feature = "properties"))]
+        {
+            builder = builder.configure_files(false);
+        }
+        for (k, v) in props {
+            builder = builder.set(*k, *v);
+        }
+        builder
+    }
+
+    /// Build [`Salak`] with `props`, file source and system environment source are disabled.
+    pub fn build(props: &[(&str, &str)]) -> Res<Salak> {
+        Self::builder(props).build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{testing::TestEnv, *};
+
+    #[test]
+    fn test_env_test() {
+        // Process environment is not mutated, so tests running in parallel are not affected.
+        assert_eq!(
+            std::env::var("PATH").ok(),
+            Salak::new()
+                .unwrap()
+                .require::<Option<String>>("PATH")
+                .unwrap()
+        );
+        let env = TestEnv::build(&[("a", "1"), ("b", "${a}0")]).unwrap();
+        assert_eq!(None, env.require::<Option<String>>("PATH").unwrap());
+        assert_eq!(10, env.require::<u8>("b").unwrap());
+        let env = TestEnv::builder(&[("a", "1")])
+            .set("a", "2")
+            .build()
+            .unwrap();
+        assert_eq!(2, env.require::<u8>("a").unwrap());
+    }
+}
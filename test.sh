@@ -4,11 +4,14 @@ cargo test --verbose
 cargo test --verbose --all-features
 cargo test --verbose --lib ## Default feature
 cargo test --verbose --lib --no-default-features 
+cargo build --verbose --lib --no-default-features --features=core-parse
 cargo test --verbose --lib --no-default-features --features=toml
 cargo test --verbose --lib --no-default-features --features=yaml
+cargo test --verbose --lib --no-default-features --features=properties
 cargo test --verbose --lib --no-default-features --features=derive
 cargo test --verbose --lib --no-default-features --features=args
 cargo test --verbose --lib --no-default-features --features=app
+cargo test --verbose --lib --no-default-features --features=chrono
 
 cargo bench --all-features 
 cargo run --example salak --features='default args app log' -- -h
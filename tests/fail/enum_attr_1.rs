@@ -1,7 +1,7 @@
 use salak::*;
 
 #[derive(Debug, FromEnvironment)]
-#[salak(prefix = "xxx")]
+#[salak(desc = "xxx")]
 pub enum FailEnum {
     Fail,
 }
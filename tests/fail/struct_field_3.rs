@@ -0,0 +1,9 @@
+use salak::*;
+
+#[derive(Debug, FromEnvironment)]
+pub struct FailStruct {
+    #[salak(default = "abc")]
+    port: u16,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use salak::*;
+
+#[derive(Debug, FromEnvironment)]
+pub struct FailStruct {
+    #[salak(required, default = "80")]
+    port: Option<u16>,
+}
+
+fn main() {}
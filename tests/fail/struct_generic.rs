@@ -0,0 +1,13 @@
+use salak::*;
+
+pub struct NotConfig;
+
+#[derive(FromEnvironment)]
+pub struct Wrapper<T> {
+    inner: T,
+}
+
+fn main() {
+    let env = Salak::new().unwrap();
+    let _ = env.require::<Wrapper<NotConfig>>("wrapper");
+}